@@ -0,0 +1,187 @@
+//! Colors derived from the Windows accent color and light/dark app mode.
+//!
+//! With `accent_enabled` on, the border, badge and flash colors are replaced
+//! by a palette derived from the system settings each time the config is
+//! loaded, and again whenever the main loop notices the settings changed.
+//! The derived colors are never saved; the theme's come back when it is off.
+
+use windows::core::HSTRING;
+use windows::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD};
+
+use crate::config::{BorderColor, Config};
+use crate::contrast;
+
+const DWM_KEY: &str = r"Software\Microsoft\Windows\DWM";
+const PERSONALIZE_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize";
+
+/// Config keys replaced by the accent or high-contrast palette.
+pub const PALETTE_KEYS: [&str; 4] = ["border_color", "badge_color", "badge_inactive_color", "flash_color"];
+
+pub const WHITE: BorderColor = BorderColor::new(1.0, 1.0, 1.0, 1.0);
+pub const BLACK: BorderColor = BorderColor::new(0.0, 0.0, 0.0, 1.0);
+
+/// Contrast a badge needs against its white text.
+const MIN_TEXT_CONTRAST: f32 = 3.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SystemColors {
+    pub accent: BorderColor,
+    /// Apps use the light theme.
+    pub light_mode: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub border: BorderColor,
+    pub badge: BorderColor,
+    pub badge_inactive: BorderColor,
+    pub flash: BorderColor,
+}
+
+impl Palette {
+    pub fn apply(&self, config: &mut Config) {
+        config.border_color = self.border;
+        config.badge_color = Some(self.badge);
+        config.badge_inactive_color = self.badge_inactive;
+        config.flash_color = Some(self.flash);
+    }
+}
+
+/// Replace the colors of `config` with the accent palette, if it follows the
+/// accent and the system colors can be read.
+pub fn resolve(config: &mut Config) {
+    if !config.accent_enabled {
+        return;
+    }
+    match read_system_colors() {
+        Some(system) => derive(&system).apply(config),
+        None => log::warn!("Could not read the system accent color; keeping theme colors"),
+    }
+}
+
+/// Read the accent color and app mode from the registry.
+pub fn read_system_colors() -> Option<SystemColors> {
+    let accent = read_dword(DWM_KEY, "AccentColor")?;
+    Some(SystemColors { accent: color_from_abgr(accent), light_mode: light_mode() })
+}
+
+/// Whether apps use the light theme.
+pub fn light_mode() -> bool {
+    // Missing on older builds, which only have the light mode
+    read_dword(PERSONALIZE_KEY, "AppsUseLightTheme").is_none_or(|v| v != 0)
+}
+
+fn read_dword(key: &str, value: &str) -> Option<u32> {
+    let mut data = 0u32;
+    let mut size = std::mem::size_of::<u32>() as u32;
+    let result = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            &HSTRING::from(key),
+            &HSTRING::from(value),
+            RRF_RT_REG_DWORD,
+            None,
+            Some(&mut data as *mut u32 as *mut _),
+            Some(&mut size),
+        )
+    };
+    result.is_ok().then_some(data)
+}
+
+/// Registry accent colors (and COLORREFs) are stored as 0xAABBGGRR; the
+/// alpha byte is unused.
+pub fn color_from_abgr(value: u32) -> BorderColor {
+    let channel = |shift: u32| ((value >> shift) & 0xFF) as f32 / 255.0;
+    BorderColor::new(channel(0), channel(8), channel(16), 1.0)
+}
+
+/// Palette for the accent color and app mode. Dark mode gets a lighter
+/// border so it stands out on dark window chrome, light mode a deeper one.
+pub fn derive(system: &SystemColors) -> Palette {
+    let accent = system.accent;
+
+    let (border, flash, inactive_base) = if system.light_mode {
+        (mix(accent, BLACK, 0.15), accent, BorderColor::new(0.55, 0.55, 0.55, 1.0))
+    } else {
+        (mix(accent, WHITE, 0.25), mix(accent, WHITE, 0.4), BorderColor::new(0.25, 0.25, 0.25, 1.0))
+    };
+
+    // Darken in steps until white badge text is legible
+    let mut badge = accent;
+    while contrast::contrast_ratio(badge, WHITE) < MIN_TEXT_CONTRAST {
+        badge = mix(badge, BLACK, 0.1);
+    }
+
+    Palette {
+        border,
+        badge,
+        badge_inactive: BorderColor { a: 0.6, ..mix(accent, inactive_base, 0.75) },
+        flash,
+    }
+}
+
+/// Blend `t` of the way from `from` to `to`; keeps `from`'s alpha.
+pub fn mix(from: BorderColor, to: BorderColor, t: f32) -> BorderColor {
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    BorderColor::new(lerp(from.r, to.r), lerp(from.g, to.g), lerp(from.b, to.b), from.a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contrast::luminance;
+
+    const BLUE: u32 = 0xFFD77800; // Default Windows accent, #0078D7
+    const YELLOW: u32 = 0xFF00EEFF;
+
+    fn system(abgr: u32, light_mode: bool) -> SystemColors {
+        SystemColors { accent: color_from_abgr(abgr), light_mode }
+    }
+
+    #[test]
+    fn registry_value_is_abgr() {
+        let c = color_from_abgr(BLUE);
+        assert_eq!((c.r, c.g, c.b), (0.0, 120.0 / 255.0, 215.0 / 255.0));
+    }
+
+    #[test]
+    fn dark_mode_border_is_lighter() {
+        let dark = derive(&system(BLUE, false));
+        let light = derive(&system(BLUE, true));
+        assert!(luminance(dark.border) > luminance(light.border));
+        assert!(luminance(dark.border) > luminance(color_from_abgr(BLUE)));
+        assert_eq!(dark.border.a, 1.0);
+    }
+
+    #[test]
+    fn bright_accent_badge_is_darkened_for_white_text() {
+        let palette = derive(&system(YELLOW, true));
+        assert!(contrast::contrast_ratio(palette.badge, WHITE) >= MIN_TEXT_CONTRAST);
+        assert!(palette.badge.r > palette.badge.b, "badge keeps the accent hue");
+    }
+
+    #[test]
+    fn dark_accent_badge_is_unchanged() {
+        let palette = derive(&system(0xFF602000, false));
+        assert_eq!(palette.badge, color_from_abgr(0xFF602000));
+    }
+
+    #[test]
+    fn inactive_badge_is_muted_and_translucent() {
+        let accent = color_from_abgr(BLUE);
+        for light_mode in [false, true] {
+            let inactive = derive(&system(BLUE, light_mode)).badge_inactive;
+            assert_eq!(inactive.a, 0.6);
+            assert!(inactive.b - inactive.r < accent.b - accent.r);
+        }
+    }
+
+    #[test]
+    fn palette_replaces_config_colors() {
+        let mut config = Config::default();
+        let palette = derive(&system(BLUE, false));
+        palette.apply(&mut config);
+        assert_eq!(config.effective_badge_color(), palette.badge);
+        assert_eq!(config.effective_flash_color(), palette.flash);
+    }
+}
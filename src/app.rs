@@ -1,7 +1,9 @@
 use windows::Win32::Foundation::RECT;
 
 use crate::config::Config;
+use crate::cursor::warp::CursorMemory;
 use crate::monitor::enumeration::MonitorInfo;
+use crate::theme::ThemeColors;
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -18,18 +20,25 @@ pub struct FocusState {
 /// Top-level application state managed by the message loop.
 pub struct App {
     pub config: Config,
+    /// The colors of `config` before the accent or high-contrast palette
+    /// replaced them; restored when neither applies anymore.
+    pub theme_colors: ThemeColors,
     pub focus: Option<FocusState>,
     pub prev_monitor_index: Option<usize>,
     pub monitors: Vec<MonitorInfo>,
+    /// Cursor positions inside recently focused windows, for warping back.
+    pub cursor_memory: CursorMemory,
 }
 
 impl App {
-    pub fn new(config: Config) -> Self {
+    pub fn new(config: Config, theme_colors: ThemeColors) -> Self {
         Self {
             config,
+            theme_colors,
             focus: None,
             prev_monitor_index: None,
             monitors: Vec::new(),
+            cursor_memory: CursorMemory::default(),
         }
     }
 
@@ -0,0 +1,488 @@
+//! Colors as written in the config file. Besides the `{ r, g, b, a }` float
+//! table, a color can be a string: hex (`"#0078D6"`, `"#0078D6E6"`, or the
+//! short `"#07D"`), `rgb()`/`rgba()`, `hsl()`/`hsla()` or a CSS color name.
+//!
+//! Colors are held as floats; saving writes each one back in the form the
+//! config file already used for it (see `restore_forms`).
+
+use crate::config::BorderColor;
+
+/// How a color was written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorForm {
+    Hex,
+    Rgb,
+    Hsl,
+    Name,
+}
+
+/// Parse a color string, returning the color and the form it was written in.
+pub fn parse(text: &str) -> Option<(BorderColor, ColorForm)> {
+    let text = text.trim();
+    if let Some(hex) = text.strip_prefix('#') {
+        return parse_hex(hex).map(|c| (c, ColorForm::Hex));
+    }
+    if let Some((func, args)) = text.strip_suffix(')').and_then(|t| t.split_once('(')) {
+        let args: Vec<&str> = args
+            .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+            .filter(|a| !a.is_empty())
+            .collect();
+        return match func.trim().to_ascii_lowercase().as_str() {
+            "rgb" | "rgba" => parse_rgb(&args).map(|c| (c, ColorForm::Rgb)),
+            "hsl" | "hsla" => parse_hsl(&args).map(|c| (c, ColorForm::Hsl)),
+            _ => None,
+        };
+    }
+    let name = text.to_ascii_lowercase();
+    if name == "transparent" {
+        return Some((BorderColor::new(0.0, 0.0, 0.0, 0.0), ColorForm::Name));
+    }
+    NAMED_COLORS
+        .iter()
+        .find(|&&(n, _)| n == name)
+        .map(|&(_, rgb)| (from_rgb_u32(rgb), ColorForm::Name))
+}
+
+/// Write `color` in `form`. A name is used only while the color still
+/// matches one exactly; otherwise it falls back to hex.
+pub fn format(color: BorderColor, form: ColorForm) -> String {
+    let opaque = to_byte(color.a) == 255;
+    match form {
+        ColorForm::Hex => hex(color),
+        ColorForm::Rgb => {
+            let (r, g, b) = (to_byte(color.r), to_byte(color.g), to_byte(color.b));
+            if opaque {
+                format!("rgb({}, {}, {})", r, g, b)
+            } else {
+                format!("rgba({}, {}, {}, {})", r, g, b, decimal(color.a, 100.0))
+            }
+        }
+        ColorForm::Hsl => {
+            let (h, s, l) = to_hsl(color);
+            let (h, s, l) = (decimal(h, 10.0), decimal(s * 100.0, 10.0), decimal(l * 100.0, 10.0));
+            if opaque {
+                format!("hsl({}, {}%, {}%)", h, s, l)
+            } else {
+                format!("hsla({}, {}%, {}%, {})", h, s, l, decimal(color.a, 100.0))
+            }
+        }
+        ColorForm::Name => {
+            let rgb = (to_byte(color.r) as u32) << 16 | (to_byte(color.g) as u32) << 8 | to_byte(color.b) as u32;
+            let name = NAMED_COLORS.iter().find(|&&(_, c)| c == rgb).map(|&(n, _)| n);
+            match name {
+                Some(name) if opaque => name.to_string(),
+                _ if to_byte(color.a) == 0 && rgb == 0 => "transparent".to_string(),
+                _ => hex(color),
+            }
+        }
+    }
+}
+
+/// Write the colors of a serialized config (`new`, all float tables) as
+/// strings wherever the previous file (`old`) had a color string at the
+/// same place, in that string's form. Walks nested tables and arrays, so
+/// per-monitor colors and gradient stops keep their form too.
+pub fn restore_forms(new: &mut toml::Table, old: &toml::Table) {
+    for (key, value) in new.iter_mut() {
+        if let Some(previous) = old.get(key) {
+            restore_value(value, previous);
+        }
+    }
+}
+
+fn restore_value(new: &mut toml::Value, old: &toml::Value) {
+    match (&mut *new, old) {
+        (toml::Value::Table(table), toml::Value::String(text)) => {
+            let Some((_, form)) = parse(text) else { return };
+            if let Ok(color) = table.clone().try_into::<BorderColor>() {
+                *new = toml::Value::String(format(color, form));
+            }
+        }
+        (toml::Value::Table(table), toml::Value::Table(previous)) => restore_forms(table, previous),
+        (toml::Value::Array(items), toml::Value::Array(previous)) => {
+            for (item, prev) in items.iter_mut().zip(previous) {
+                restore_value(item, prev);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<BorderColor> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digits: Vec<u8> = match hex.len() {
+        // Short forms repeat each digit: #07D = #0077DD
+        3 | 4 => hex.chars().map(|c| c.to_digit(16).unwrap() as u8 * 17).collect(),
+        6 | 8 => (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+            .collect::<Option<_>>()?,
+        _ => return None,
+    };
+    let channel = |i: usize| digits.get(i).map_or(1.0, |&d| d as f32 / 255.0);
+    Some(BorderColor::new(channel(0), channel(1), channel(2), channel(3)))
+}
+
+fn parse_rgb(args: &[&str]) -> Option<BorderColor> {
+    if !(3..=4).contains(&args.len()) {
+        return None;
+    }
+    let channel = |arg: &str| match arg.strip_suffix('%') {
+        Some(pct) => pct.parse::<f32>().ok().map(|p| p / 100.0),
+        None => arg.parse::<f32>().ok().map(|v| v / 255.0),
+    };
+    let alpha = args.get(3).map_or(Some(1.0), |a| parse_alpha(a))?;
+    Some(BorderColor::new(
+        channel(args[0])?.clamp(0.0, 1.0),
+        channel(args[1])?.clamp(0.0, 1.0),
+        channel(args[2])?.clamp(0.0, 1.0),
+        alpha,
+    ))
+}
+
+fn parse_hsl(args: &[&str]) -> Option<BorderColor> {
+    if !(3..=4).contains(&args.len()) {
+        return None;
+    }
+    let hue: f32 = args[0].strip_suffix("deg").unwrap_or(args[0]).parse().ok()?;
+    let percent = |arg: &str| {
+        let value: f32 = arg.strip_suffix('%').unwrap_or(arg).parse().ok()?;
+        Some((value / 100.0).clamp(0.0, 1.0))
+    };
+    let alpha = args.get(3).map_or(Some(1.0), |a| parse_alpha(a))?;
+    let (r, g, b) = from_hsl(hue, percent(args[1])?, percent(args[2])?);
+    Some(BorderColor::new(r, g, b, alpha))
+}
+
+/// Alpha as a 0–1 number or a percentage.
+fn parse_alpha(arg: &str) -> Option<f32> {
+    let alpha = match arg.strip_suffix('%') {
+        Some(pct) => pct.parse::<f32>().ok()? / 100.0,
+        None => arg.parse::<f32>().ok()?,
+    };
+    Some(alpha.clamp(0.0, 1.0))
+}
+
+fn from_hsl(hue: f32, s: f32, l: f32) -> (f32, f32, f32) {
+    let h = hue.rem_euclid(360.0) / 60.0;
+    let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = l - chroma / 2.0;
+    (r + m, g + m, b + m)
+}
+
+/// Hue in degrees, saturation and lightness in 0–1.
+fn to_hsl(color: BorderColor) -> (f32, f32, f32) {
+    let (r, g, b) = (color.r, color.g, color.b);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d == 0.0 {
+        return (0.0, 0.0, l);
+    }
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        ((g - b) / d).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    (h * 60.0, s, l)
+}
+
+fn hex(color: BorderColor) -> String {
+    let (r, g, b, a) = (to_byte(color.r), to_byte(color.g), to_byte(color.b), to_byte(color.a));
+    if a == 255 {
+        format!("#{:02X}{:02X}{:02X}", r, g, b)
+    } else {
+        format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+    }
+}
+
+fn to_byte(channel: f32) -> u8 {
+    (channel.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// `value` rounded to `1 / scale`, without trailing zeros.
+fn decimal(value: f32, scale: f32) -> String {
+    format!("{}", (value * scale).round() / scale)
+}
+
+fn from_rgb_u32(rgb: u32) -> BorderColor {
+    let channel = |shift: u32| ((rgb >> shift) & 0xFF) as f32 / 255.0;
+    BorderColor::new(channel(16), channel(8), channel(0), 1.0)
+}
+
+/// CSS named colors. Where two names share a value (gray/grey, aqua/cyan,
+/// fuchsia/magenta) the first is the one written back.
+const NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xF0F8FF),
+    ("antiquewhite", 0xFAEBD7),
+    ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4),
+    ("azure", 0xF0FFFF),
+    ("beige", 0xF5F5DC),
+    ("bisque", 0xFFE4C4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xFFEBCD),
+    ("blue", 0x0000FF),
+    ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A),
+    ("burlywood", 0xDEB887),
+    ("cadetblue", 0x5F9EA0),
+    ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E),
+    ("coral", 0xFF7F50),
+    ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC),
+    ("crimson", 0xDC143C),
+    ("cyan", 0x00FFFF),
+    ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B),
+    ("darkgoldenrod", 0xB8860B),
+    ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xA9A9A9),
+    ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B),
+    ("darkolivegreen", 0x556B2F),
+    ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC),
+    ("darkred", 0x8B0000),
+    ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F),
+    ("darkslateblue", 0x483D8B),
+    ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F),
+    ("darkturquoise", 0x00CED1),
+    ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493),
+    ("deepskyblue", 0x00BFFF),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF),
+    ("firebrick", 0xB22222),
+    ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22),
+    ("fuchsia", 0xFF00FF),
+    ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF),
+    ("gold", 0xFFD700),
+    ("goldenrod", 0xDAA520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xADFF2F),
+    ("grey", 0x808080),
+    ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4),
+    ("indianred", 0xCD5C5C),
+    ("indigo", 0x4B0082),
+    ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C),
+    ("lavender", 0xE6E6FA),
+    ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00),
+    ("lemonchiffon", 0xFFFACD),
+    ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080),
+    ("lightcyan", 0xE0FFFF),
+    ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3),
+    ("lightgreen", 0x90EE90),
+    ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1),
+    ("lightsalmon", 0xFFA07A),
+    ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE),
+    ("lightyellow", 0xFFFFE0),
+    ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32),
+    ("linen", 0xFAF0E6),
+    ("magenta", 0xFF00FF),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA),
+    ("mediumblue", 0x0000CD),
+    ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB),
+    ("mediumseagreen", 0x3CB371),
+    ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A),
+    ("mediumturquoise", 0x48D1CC),
+    ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xF5FFFA),
+    ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5),
+    ("navajowhite", 0xFFDEAD),
+    ("navy", 0x000080),
+    ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6B8E23),
+    ("orange", 0xFFA500),
+    ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6),
+    ("palegoldenrod", 0xEEE8AA),
+    ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE),
+    ("palevioletred", 0xDB7093),
+    ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9),
+    ("peru", 0xCD853F),
+    ("pink", 0xFFC0CB),
+    ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F),
+    ("royalblue", 0x4169E1),
+    ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072),
+    ("sandybrown", 0xF4A460),
+    ("seagreen", 0x2E8B57),
+    ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D),
+    ("silver", 0xC0C0C0),
+    ("skyblue", 0x87CEEB),
+    ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xFFFAFA),
+    ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4),
+    ("tan", 0xD2B48C),
+    ("teal", 0x008080),
+    ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347),
+    ("turquoise", 0x40E0D0),
+    ("violet", 0xEE82EE),
+    ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF),
+    ("whitesmoke", 0xF5F5F5),
+    ("yellow", 0xFFFF00),
+    ("yellowgreen", 0x9ACD32),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgba(text: &str) -> (u8, u8, u8, u8) {
+        let (c, _) = parse(text).unwrap_or_else(|| panic!("failed to parse {}", text));
+        (to_byte(c.r), to_byte(c.g), to_byte(c.b), to_byte(c.a))
+    }
+
+    #[test]
+    fn hex_forms() {
+        assert_eq!(rgba("#0078D6"), (0, 120, 214, 255));
+        assert_eq!(rgba("#0078d6e6"), (0, 120, 214, 230));
+        assert_eq!(rgba("#07D"), (0, 119, 221, 255));
+        assert_eq!(rgba("#07D8"), (0, 119, 221, 136));
+        assert!(parse("#0078D").is_none());
+        assert!(parse("#00GG00").is_none());
+    }
+
+    #[test]
+    fn rgb_and_hsl_functions() {
+        assert_eq!(rgba("rgb(0, 120, 214)"), (0, 120, 214, 255));
+        assert_eq!(rgba("rgba(0, 120, 214, 0.9)"), (0, 120, 214, 230));
+        assert_eq!(rgba("rgb(0 120 214 / 50%)"), (0, 120, 214, 128));
+        assert_eq!(rgba("RGB(100%, 0%, 50%)"), (255, 0, 128, 255));
+        assert_eq!(rgba("hsl(0, 100%, 50%)"), (255, 0, 0, 255));
+        assert_eq!(rgba("hsl(120deg 100% 25%)"), (0, 128, 0, 255));
+        assert_eq!(rgba("hsla(240, 100%, 50%, 0.5)"), (0, 0, 255, 128));
+        assert!(parse("rgb(1, 2)").is_none());
+        assert!(parse("cmyk(0, 0, 0, 0)").is_none());
+    }
+
+    #[test]
+    fn css_names() {
+        assert_eq!(rgba("rebeccapurple"), (102, 51, 153, 255));
+        assert_eq!(rgba("DodgerBlue"), (30, 144, 255, 255));
+        assert_eq!(rgba("transparent"), (0, 0, 0, 0));
+        assert!(parse("notacolor").is_none());
+        // Sorted and unique
+        assert!(NAMED_COLORS.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn written_form_round_trips() {
+        for text in ["#0078D6", "#0078D6E6", "rgb(0, 120, 214)", "rgba(0, 120, 214, 0.9)", "hsl(206.4, 100%, 42%)", "orange"] {
+            let (color, form) = parse(text).unwrap();
+            assert_eq!(format(color, form), text);
+        }
+    }
+
+    #[test]
+    fn changed_named_color_falls_back_to_hex() {
+        let (color, form) = parse("orange").unwrap();
+        let darker = BorderColor::new(color.r * 0.5, color.g * 0.5, color.b, 1.0);
+        assert_eq!(format(darker, form), "#805300");
+        assert_eq!(format(BorderColor { a: 0.5, ..color }, form), "#FFA50080");
+    }
+
+    #[test]
+    fn saved_colors_keep_the_written_form() {
+        let old: toml::Table = toml::from_str(
+            r##"
+            border_color = "#0078D6"
+            flash_color = { r = 1.0, g = 0.0, b = 0.0, a = 1.0 }
+            gradient_stops = ["red", { r = 0.0, g = 0.0, b = 1.0, a = 1.0 }]
+            [monitors.DISPLAY2]
+            border_color = "hsl(120, 100%, 25%)"
+            "##,
+        )
+        .unwrap();
+        let mut new: toml::Table = toml::from_str(
+            r#"
+            border_color = { r = 1.0, g = 0.0, b = 0.0, a = 1.0 }
+            flash_color = { r = 0.0, g = 1.0, b = 0.0, a = 1.0 }
+            badge_color = { r = 0.0, g = 0.0, b = 1.0, a = 1.0 }
+            gradient_stops = [{ r = 1.0, g = 0.0, b = 0.0, a = 1.0 }, { r = 0.0, g = 0.0, b = 1.0, a = 1.0 }]
+            [monitors.DISPLAY2]
+            border_color = { r = 0.0, g = 0.0, b = 1.0, a = 1.0 }
+            "#,
+        )
+        .unwrap();
+        restore_forms(&mut new, &old);
+
+        assert_eq!(new["border_color"].as_str(), Some("#FF0000"));
+        assert!(new["flash_color"].is_table());
+        assert!(new["badge_color"].is_table());
+        let stops = new["gradient_stops"].as_array().unwrap();
+        assert_eq!(stops[0].as_str(), Some("red"));
+        assert!(stops[1].is_table());
+        assert_eq!(new["monitors"]["DISPLAY2"]["border_color"].as_str(), Some("hsl(240, 100%, 50%)"));
+    }
+
+    #[test]
+    fn config_accepts_every_form() {
+        let config: crate::config::Config = toml::from_str(
+            r##"
+            border_color = "#0078D6E6"
+            flash_color = "tomato"
+            badge_inactive_color = { r = 0.35, g = 0.35, b = 0.35, a = 0.6 }
+            "##,
+        )
+        .unwrap();
+        assert_eq!(to_byte(config.border_color.a), 230);
+        assert_eq!(config.flash_color.map(|c| to_byte(c.g)), Some(99));
+        assert_eq!(config.badge_inactive_color.a, 0.6);
+        assert!(toml::from_str::<crate::config::Config>(r#"border_color = "nope""#).is_err());
+    }
+}
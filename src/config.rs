@@ -1,19 +1,49 @@
 /// Application configuration (colors, hotkey, toggles).
 /// All values have compile-time defaults; runtime changes via tray menu or settings panel.
 
-use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+use crate::color;
+use crate::theme;
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum BorderStyle {
     Solid,
     Glow,
+    /// Solid frame that blinks briefly on each focus change, then settles.
+    Pulse,
+    /// Solid frame whose opacity slowly rises and falls while shown.
+    Breathing,
+    /// Frame colored by `Config::gradient_stops` around its center, optionally rotating.
+    Gradient,
+    /// Lighter frame of `dash_length` dashes separated by `dash_gap`.
+    Dashed,
+    /// Round dots one border-thickness wide, separated by `dash_gap`.
+    Dotted,
 }
 
 impl BorderStyle {
+    pub const ALL: [Self; 7] = [
+        Self::Solid,
+        Self::Glow,
+        Self::Pulse,
+        Self::Breathing,
+        Self::Gradient,
+        Self::Dashed,
+        Self::Dotted,
+    ];
+
     pub fn next(self) -> Self {
         match self {
             Self::Solid => Self::Glow,
-            Self::Glow => Self::Solid,
+            Self::Glow => Self::Pulse,
+            Self::Pulse => Self::Breathing,
+            Self::Breathing => Self::Gradient,
+            Self::Gradient => Self::Dashed,
+            Self::Dashed => Self::Dotted,
+            Self::Dotted => Self::Solid,
         }
     }
 
@@ -21,28 +51,420 @@ impl BorderStyle {
         match self {
             Self::Solid => "Solid",
             Self::Glow => "Glow",
+            Self::Pulse => "Pulse",
+            Self::Breathing => "Breathing",
+            Self::Gradient => "Gradient",
+            Self::Dashed => "Dashed",
+            Self::Dotted => "Dotted",
+        }
+    }
+}
+
+/// How the border's corners are shaped.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CornerStyle {
+    /// Follow the window's DWM corner preference (rounded on Windows 11).
+    Auto,
+    Square,
+    /// Use `Config::corner_radius` for every window.
+    Manual,
+}
+
+impl CornerStyle {
+    pub const ALL: [Self; 3] = [Self::Auto, Self::Square, Self::Manual];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Auto => "Auto",
+            Self::Square => "Square",
+            Self::Manual => "Manual",
+        }
+    }
+}
+
+/// Easing curve for border transitions between windows.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Easing {
+    Linear,
+    EaseOut,
+    EaseInOut,
+    /// Slight overshoot past the target before settling.
+    EaseOutBack,
+}
+
+impl Easing {
+    pub const ALL: [Self; 4] = [Self::Linear, Self::EaseOut, Self::EaseInOut, Self::EaseOutBack];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Linear => "Linear",
+            Self::EaseOut => "Ease out",
+            Self::EaseInOut => "Ease in-out",
+            Self::EaseOutBack => "Ease out (back)",
+        }
+    }
+}
+
+/// How quickly the Glow style fades from the border color to transparent.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GlowFalloff {
+    Linear,
+    Quadratic,
+    /// Bright core with a soft, long tail.
+    Gaussian,
+}
+
+impl GlowFalloff {
+    pub const ALL: [Self; 3] = [Self::Linear, Self::Quadratic, Self::Gaussian];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Linear => "Linear",
+            Self::Quadratic => "Quadratic",
+            Self::Gaussian => "Gaussian",
+        }
+    }
+}
+
+/// How overlay windows are composited onto the desktop.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RenderMode {
+    /// Real per-pixel alpha: soft glow, anti-aliased edges, translucent badges.
+    PerPixel,
+    /// Magenta color key with uniform window alpha. Fallback for setups where
+    /// per-pixel layered windows misbehave (some remote desktop sessions).
+    ColorKey,
+}
+
+impl RenderMode {
+    pub const ALL: [Self; 2] = [Self::PerPixel, Self::ColorKey];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::PerPixel => "Per-pixel alpha",
+            Self::ColorKey => "Color key (compatibility)",
+        }
+    }
+}
+
+/// How the flash is drawn over its target.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FlashShape {
+    /// Solid fill of the whole target.
+    #[serde(alias = "FullScreen")]
+    Fill,
+    /// Glowing bands along the target's edges.
+    Edges,
+    /// Soft glow from each corner.
+    Corners,
+}
+
+impl FlashShape {
+    pub const ALL: [Self; 3] = [Self::Fill, Self::Edges, Self::Corners];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Fill => "Fill",
+            Self::Edges => "Edges",
+            Self::Corners => "Corner vignette",
+        }
+    }
+}
+
+/// Where the flash appears when focus moves.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FlashTarget {
+    Monitor,
+    /// Only the newly focused window.
+    Window,
+    /// The monitor, with the window drawn stronger on top.
+    Both,
+}
+
+impl FlashTarget {
+    pub const ALL: [Self; 3] = [Self::Monitor, Self::Window, Self::Both];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Monitor => "Monitor",
+            Self::Window => "Focused window",
+            Self::Both => "Monitor and window",
+        }
+    }
+}
+
+/// Monitor corner a badge is anchored to.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BadgeCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl BadgeCorner {
+    pub const ALL: [Self; 4] = [Self::TopLeft, Self::TopRight, Self::BottomLeft, Self::BottomRight];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::TopLeft => "Top left",
+            Self::TopRight => "Top right",
+            Self::BottomLeft => "Bottom left",
+            Self::BottomRight => "Bottom right",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BadgeShape {
+    RoundedRect,
+    /// Diameter is the smaller of the badge width and height.
+    Circle,
+    /// Fully rounded ends.
+    Pill,
+}
+
+impl BadgeShape {
+    pub const ALL: [Self; 3] = [Self::RoundedRect, Self::Circle, Self::Pill];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::RoundedRect => "Rounded rectangle",
+            Self::Circle => "Circle",
+            Self::Pill => "Pill",
+        }
+    }
+}
+
+/// What the monitor indicator shows.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum IndicatorMode {
+    /// One numbered badge per monitor.
+    Badges,
+    /// Scaled map of the whole monitor layout.
+    MiniMap,
+}
+
+impl IndicatorMode {
+    pub const ALL: [Self; 2] = [Self::Badges, Self::MiniMap];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Badges => "Badges",
+            Self::MiniMap => "Mini-map",
+        }
+    }
+}
+
+/// Which monitors show the mini-map.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MiniMapScope {
+    EveryMonitor,
+    ActiveOnly,
+}
+
+impl MiniMapScope {
+    pub const ALL: [Self; 2] = [Self::EveryMonitor, Self::ActiveOnly];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::EveryMonitor => "Every monitor",
+            Self::ActiveOnly => "Active monitor only",
+        }
+    }
+}
+
+/// Where the cursor lands when warped to the focused window.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WarpTarget {
+    Center,
+    /// Where the cursor was when the window last lost focus, else the center.
+    LastPosition,
+}
+
+impl WarpTarget {
+    pub const ALL: [Self; 2] = [Self::Center, Self::LastPosition];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Center => "Window center",
+            Self::LastPosition => "Last cursor position",
         }
     }
 }
 
+/// When the border, badge and flash colors switch to the high-contrast palette.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HighContrast {
+    Off,
+    On,
+    /// While Windows high contrast is turned on.
+    FollowSystem,
+}
+
+impl HighContrast {
+    pub const ALL: [Self; 3] = [Self::Off, Self::On, Self::FollowSystem];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::On => "On",
+            Self::FollowSystem => "Follow Windows",
+        }
+    }
+}
+
+/// Badge look and placement after per-monitor overrides are applied.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BadgeStyle {
+    pub corner: BadgeCorner,
+    /// Distance in px from the corner's vertical and horizontal monitor edges.
+    pub offset_x: i32,
+    pub offset_y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub shape: BadgeShape,
+    pub font_size: f32,
+}
+
+/// Per-monitor overrides under `[monitors.<id>]`, where `<id>` is the device
+/// name without its `\\.\` prefix (e.g. `DISPLAY2`). Unset values fall back to
+/// the global setting.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonitorConfig {
+    /// Name shown by `{label}` in badge templates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge_template: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge_corner: Option<BadgeCorner>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge_offset_x: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge_offset_y: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge_width: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge_height: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge_shape: Option<BadgeShape>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge_font_size: Option<f32>,
+    /// Border color while a window on this monitor has focus.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border_color: Option<BorderColor>,
+    /// Badge color while this monitor has focus; follows the monitor's
+    /// border color when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge_color: Option<BorderColor>,
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub border_enabled: bool,
     pub flash_enabled: bool,
     pub indicator_enabled: bool,
+    /// Name of the theme the themed values below start from; the config
+    /// file only stores the values that differ from it.
+    pub theme: String,
+    /// Take the border, badge and flash colors from the Windows accent color
+    /// and light/dark mode instead of the theme.
+    pub accent_enabled: bool,
+    pub high_contrast: HighContrast,
     pub border_color: BorderColor,
     pub border_thickness: f32,
     pub border_style: BorderStyle,
+    /// Colors spread evenly around the border for the Gradient style (at least two).
+    /// Kept apart from `border_color`, which the other styles, the badges,
+    /// the flash, themes, per-monitor overrides and the accent and
+    /// high-contrast palettes all read as a single color.
+    pub gradient_stops: Vec<BorderColor>,
+    /// Spin the gradient around the window instead of keeping it still.
+    pub gradient_rotate: bool,
+    /// Time for one full revolution when `gradient_rotate` is on.
+    pub gradient_rotation_ms: u32,
+    /// Dash length in px for the Dashed style.
+    pub dash_length: f32,
+    /// Gap between dashes (Dashed) or dots (Dotted) in px.
+    pub dash_gap: f32,
+    /// Total width of the Glow style in px.
+    pub glow_width: f32,
+    /// Number of bands the glow is drawn with; more bands give a smoother fade.
+    pub glow_layers: u32,
+    pub glow_falloff: GlowFalloff,
+    pub corner_style: CornerStyle,
+    /// Corner radius in px (at 96 DPI) used when `corner_style` is Manual.
+    pub corner_radius: f32,
+    /// Slide the border from the old window to the new one on focus change.
+    pub animate_transitions: bool,
+    pub animation_duration_ms: u32,
+    pub animation_easing: Easing,
+    /// Time from the flash appearing until it is gone.
     pub flash_duration_ms: u32,
+    /// How much of the end of `flash_duration_ms` is spent fading out.
+    pub flash_fade_ms: u32,
     pub flash_opacity: f32,
+    /// Flash color; follows `border_color` when unset.
+    #[serde(deserialize_with = "deserialize_follow_border")]
+    pub flash_color: Option<BorderColor>,
+    pub flash_shape: FlashShape,
+    pub flash_target: FlashTarget,
+    /// Also give the window a subtle flash when focus changes within a monitor.
+    pub flash_same_monitor: bool,
+    /// Dim every monitor except the focused window.
+    pub spotlight_enabled: bool,
+    pub spotlight_opacity: f32,
+    /// Label on the border's top edge naming the focused app.
+    pub title_tab_enabled: bool,
+    pub title_tab_show_title: bool,
+    pub title_tab_show_monitor: bool,
+    pub indicator_mode: IndicatorMode,
+    /// Mini-map width in px; it sits at the badge corner and offset.
+    pub minimap_width: i32,
+    pub minimap_scope: MiniMapScope,
+    pub badge_corner: BadgeCorner,
+    pub badge_offset_x: i32,
+    pub badge_offset_y: i32,
+    pub badge_width: i32,
+    pub badge_height: i32,
+    pub badge_shape: BadgeShape,
+    pub badge_font_size: f32,
+    /// Badge color on the focused monitor; follows `border_color` when unset.
+    #[serde(deserialize_with = "deserialize_follow_border")]
+    pub badge_color: Option<BorderColor>,
+    /// Badge color on the other monitors, alpha included.
+    pub badge_inactive_color: BorderColor,
+    /// Badge text: `{n}` monitor number, `{label}` monitor label, `{w}`/`{h}`
+    /// resolution, `{windows}` number of app windows on the monitor.
+    pub badge_template: String,
     pub reveal_hotkey_enabled: bool,
+    pub picker_hotkey_enabled: bool,
+    pub locator_hotkey_enabled: bool,
+    /// Ripple around the cursor when the mouse is shaken.
+    pub locator_on_shake: bool,
+    /// Ripple around the cursor when it moves onto another monitor.
+    pub locator_on_monitor_change: bool,
+    /// Move the cursor to the focused window when the keyboard switches monitors.
+    pub warp_on_monitor_change: bool,
+    pub warp_target: WarpTarget,
+    /// Exe names (e.g. `game.exe`) whose windows never pull the cursor.
+    pub warp_exclude: Vec<String>,
+    pub warp_hotkey_enabled: bool,
     pub poll_interval_ms: u32,
-    #[serde(default)]
+    pub render_mode: RenderMode,
     pub auto_start: bool,
+    /// Per-monitor overrides keyed by monitor id.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub monitors: BTreeMap<String, MonitorConfig>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// Saved as an `{ r, g, b, a }` table; also read from hex, `rgb()`, `hsl()`
+/// and CSS color name strings (see `color`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct BorderColor {
     pub r: f32,
     pub g: f32,
@@ -56,20 +478,179 @@ impl BorderColor {
     }
 }
 
+impl<'de> Deserialize<'de> for BorderColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Written {
+            Text(String),
+            Table { r: f32, g: f32, b: f32, a: f32 },
+        }
+
+        match Written::deserialize(deserializer)? {
+            Written::Table { r, g, b, a } => Ok(Self::new(r, g, b, a)),
+            Written::Text(text) => color::parse(&text)
+                .map(|(color, _)| color)
+                .ok_or_else(|| de::Error::custom(format!("invalid color \"{}\"", text))),
+        }
+    }
+}
+
+/// Written for `badge_color` or `flash_color` when it follows the border color
+/// but the theme sets one, so the theme's color doesn't come back on load.
+pub const FOLLOW_BORDER: &str = "border";
+
+/// Reads an optional color that may also be written as `FOLLOW_BORDER`.
+pub fn deserialize_follow_border<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<BorderColor>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Written {
+        Text(String),
+        Color(BorderColor),
+    }
+
+    match Option::<Written>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Written::Text(text)) if text.eq_ignore_ascii_case(FOLLOW_BORDER) => Ok(None),
+        Some(Written::Text(text)) => color::parse(&text)
+            .map(|(color, _)| Some(color))
+            .ok_or_else(|| de::Error::custom(format!("invalid color \"{}\"", text))),
+        Some(Written::Color(color)) => Ok(Some(color)),
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             border_enabled: true,
             flash_enabled: false,
             indicator_enabled: true,
-            border_color: BorderColor::new(0.0, 0.47, 0.84, 0.9), // Blue
+            theme: theme::DEFAULT_THEME.to_string(),
+            accent_enabled: false,
+            high_contrast: HighContrast::FollowSystem,
+            border_color: BorderColor::new(0.0, 0.47, 0.84, 1.0), // Blue
             border_thickness: 4.0,
             border_style: BorderStyle::Solid,
+            gradient_stops: vec![
+                BorderColor::new(0.0, 0.47, 0.84, 1.0), // Blue
+                BorderColor::new(0.55, 0.25, 0.85, 1.0), // Purple
+                BorderColor::new(0.0, 0.75, 0.7, 1.0), // Teal
+            ],
+            gradient_rotate: false,
+            gradient_rotation_ms: 4000,
+            dash_length: 12.0,
+            dash_gap: 6.0,
+            glow_width: 8.0,
+            glow_layers: 4,
+            glow_falloff: GlowFalloff::Quadratic,
+            corner_style: CornerStyle::Auto,
+            corner_radius: 8.0,
+            animate_transitions: false,
+            animation_duration_ms: 150,
+            animation_easing: Easing::EaseOut,
             flash_duration_ms: 150,
+            flash_fade_ms: 150,
             flash_opacity: 0.25,
+            flash_color: None,
+            flash_shape: FlashShape::Fill,
+            flash_target: FlashTarget::Monitor,
+            flash_same_monitor: false,
+            spotlight_enabled: false,
+            spotlight_opacity: 0.4,
+            title_tab_enabled: false,
+            title_tab_show_title: false,
+            title_tab_show_monitor: true,
+            indicator_mode: IndicatorMode::Badges,
+            minimap_width: 120,
+            minimap_scope: MiniMapScope::EveryMonitor,
+            badge_corner: BadgeCorner::BottomLeft,
+            badge_offset_x: 8,
+            badge_offset_y: 8,
+            badge_width: 40,
+            badge_height: 30,
+            badge_shape: BadgeShape::RoundedRect,
+            badge_font_size: 15.0,
+            badge_color: None,
+            badge_inactive_color: BorderColor::new(0.35, 0.35, 0.35, 0.6), // Gray
+            badge_template: "{n}".to_string(),
             reveal_hotkey_enabled: true,
+            picker_hotkey_enabled: true,
+            locator_hotkey_enabled: true,
+            locator_on_shake: false,
+            locator_on_monitor_change: false,
+            warp_on_monitor_change: false,
+            warp_target: WarpTarget::Center,
+            warp_exclude: Vec::new(),
+            warp_hotkey_enabled: true,
             poll_interval_ms: 100, // 10fps fallback — WM_LOCATION_CHANGED handles real-time
+            render_mode: RenderMode::PerPixel,
             auto_start: false,
+            monitors: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Color the flash is drawn in: `flash_color`, or the border color when unset.
+    pub fn effective_flash_color(&self) -> BorderColor {
+        self.flash_color.unwrap_or(self.border_color)
+    }
+
+    /// Color of the focused monitor's badge: `badge_color`, or the border
+    /// color when unset.
+    pub fn effective_badge_color(&self) -> BorderColor {
+        self.badge_color.unwrap_or(self.border_color)
+    }
+
+    /// Badge template for the monitor `monitor_id`.
+    pub fn badge_template(&self, monitor_id: &str) -> &str {
+        self.monitors
+            .get(monitor_id)
+            .and_then(|m| m.badge_template.as_deref())
+            .unwrap_or(&self.badge_template)
+    }
+
+    /// Border color while the monitor `monitor_id` has focus.
+    pub fn monitor_border_color(&self, monitor_id: &str) -> BorderColor {
+        self.monitors
+            .get(monitor_id)
+            .and_then(|m| m.border_color)
+            .unwrap_or(self.border_color)
+    }
+
+    /// Active badge color of the monitor `monitor_id`: its own badge color,
+    /// else its own border color, else the global badge color.
+    pub fn monitor_badge_color(&self, monitor_id: &str) -> BorderColor {
+        self.monitors
+            .get(monitor_id)
+            .and_then(|m| m.badge_color.or(m.border_color))
+            .unwrap_or_else(|| self.effective_badge_color())
+    }
+
+    /// Flash color on the monitor `monitor_id`: `flash_color`, or the
+    /// monitor's border color when unset.
+    pub fn monitor_flash_color(&self, monitor_id: &str) -> BorderColor {
+        self.flash_color.unwrap_or_else(|| self.monitor_border_color(monitor_id))
+    }
+
+    /// User label for the monitor `monitor_id`, if one is set.
+    pub fn monitor_label(&self, monitor_id: &str) -> Option<&str> {
+        self.monitors.get(monitor_id).and_then(|m| m.label.as_deref())
+    }
+
+    /// Badge style for the monitor `monitor_id`, with its overrides applied.
+    pub fn badge_style(&self, monitor_id: &str) -> BadgeStyle {
+        let overrides = self.monitors.get(monitor_id).cloned().unwrap_or_default();
+        BadgeStyle {
+            corner: overrides.badge_corner.unwrap_or(self.badge_corner),
+            offset_x: overrides.badge_offset_x.unwrap_or(self.badge_offset_x),
+            offset_y: overrides.badge_offset_y.unwrap_or(self.badge_offset_y),
+            width: overrides.badge_width.unwrap_or(self.badge_width),
+            height: overrides.badge_height.unwrap_or(self.badge_height),
+            shape: overrides.badge_shape.unwrap_or(self.badge_shape),
+            font_size: overrides.badge_font_size.unwrap_or(self.badge_font_size),
         }
     }
 }
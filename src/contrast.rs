@@ -0,0 +1,163 @@
+//! High-contrast palette and the contrast math behind it.
+//!
+//! While high contrast is on (always, or while Windows has it turned on),
+//! the border, badge and flash colors are replaced the same way as with the
+//! accent color. The border uses the system highlight color under Windows
+//! high contrast, otherwise a color picked against the app mode's window
+//! chrome. Badges get a strong active color over an opaque black inactive
+//! one, told apart by brightness rather than hue.
+
+use windows::Win32::Graphics::Gdi::{GetSysColor, COLOR_HIGHLIGHT};
+use windows::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW};
+use windows::Win32::UI::WindowsAndMessaging::{
+    SystemParametersInfoW, SPI_GETHIGHCONTRAST, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+};
+
+use crate::accent::{self, Palette};
+use crate::config::{BorderColor, Config, HighContrast};
+
+/// Border on light window chrome.
+const LIGHT_BORDER: BorderColor = BorderColor::new(0.0, 0.25, 0.85, 1.0);
+/// Border on dark window chrome.
+const DARK_BORDER: BorderColor = BorderColor::new(1.0, 0.85, 0.0, 1.0);
+const ACTIVE_BADGE: BorderColor = BorderColor::new(0.0, 0.45, 0.75, 1.0);
+const INACTIVE_BADGE: BorderColor = BorderColor::new(0.0, 0.0, 0.0, 1.0);
+
+/// Whether the high-contrast palette applies in `mode`.
+pub fn is_active(mode: HighContrast) -> bool {
+    match mode {
+        HighContrast::Off => false,
+        HighContrast::On => true,
+        HighContrast::FollowSystem => system_high_contrast(),
+    }
+}
+
+/// Replace the colors of `config` with the high-contrast palette while it
+/// applies.
+pub fn resolve(config: &mut Config) {
+    if !is_active(config.high_contrast) {
+        return;
+    }
+    let highlight = system_high_contrast().then(system_highlight);
+    palette(accent::light_mode(), highlight).apply(config);
+}
+
+/// Palette for the app mode. `highlight` is the Windows high-contrast
+/// highlight color, when that is on.
+pub fn palette(light_mode: bool, highlight: Option<BorderColor>) -> Palette {
+    let border = highlight.unwrap_or(if light_mode { LIGHT_BORDER } else { DARK_BORDER });
+    Palette {
+        border,
+        badge: ACTIVE_BADGE,
+        badge_inactive: INACTIVE_BADGE,
+        flash: border,
+    }
+}
+
+/// WCAG contrast ratio between two colors' RGB, from 1 (same) to 21.
+pub fn contrast_ratio(a: BorderColor, b: BorderColor) -> f32 {
+    let (la, lb) = (luminance(a), luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// WCAG relative luminance of the color's RGB.
+pub fn luminance(color: BorderColor) -> f32 {
+    let linear = |c: f32| {
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(color.r) + 0.7152 * linear(color.g) + 0.0722 * linear(color.b)
+}
+
+fn system_high_contrast() -> bool {
+    let mut hc = HIGHCONTRASTW {
+        cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+        ..Default::default()
+    };
+    let ok = unsafe {
+        SystemParametersInfoW(
+            SPI_GETHIGHCONTRAST,
+            hc.cbSize,
+            Some(&mut hc as *mut HIGHCONTRASTW as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    };
+    ok.is_ok() && hc.dwFlags.contains(HCF_HIGHCONTRASTON)
+}
+
+fn system_highlight() -> BorderColor {
+    accent::color_from_abgr(unsafe { GetSysColor(COLOR_HIGHLIGHT) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme;
+
+    fn gray(v: f32) -> BorderColor {
+        BorderColor::new(v, v, v, 1.0)
+    }
+
+    /// Title bars and frames of typical light windows.
+    fn light_chrome() -> [BorderColor; 2] {
+        [gray(1.0), gray(0.953)]
+    }
+
+    /// Title bars and frames of typical dark windows.
+    fn dark_chrome() -> [BorderColor; 2] {
+        [gray(0.125), gray(0.169)]
+    }
+
+    #[test]
+    fn ratio_bounds() {
+        assert!((contrast_ratio(accent::WHITE, accent::BLACK) - 21.0).abs() < 0.01);
+        assert_eq!(contrast_ratio(gray(0.5), gray(0.5)), 1.0);
+        assert_eq!(contrast_ratio(gray(0.2), gray(0.7)), contrast_ratio(gray(0.7), gray(0.2)));
+    }
+
+    #[test]
+    fn high_contrast_border_stands_out_on_chrome() {
+        let light = palette(true, None);
+        for chrome in light_chrome() {
+            assert!(contrast_ratio(light.border, chrome) >= 4.5);
+        }
+        let dark = palette(false, None);
+        for chrome in dark_chrome() {
+            assert!(contrast_ratio(dark.border, chrome) >= 4.5);
+        }
+    }
+
+    #[test]
+    fn high_contrast_badges_are_distinct_and_legible() {
+        let p = palette(true, None);
+        assert!(contrast_ratio(p.badge, p.badge_inactive) >= 3.0);
+        assert!(contrast_ratio(p.badge, accent::WHITE) >= 4.5);
+        assert!(contrast_ratio(p.badge_inactive, accent::WHITE) >= 4.5);
+        assert_eq!((p.badge.a, p.badge_inactive.a), (1.0, 1.0));
+    }
+
+    #[test]
+    fn system_highlight_is_the_border() {
+        let highlight = BorderColor::new(0.1, 0.9, 1.0, 1.0);
+        let p = palette(false, Some(highlight));
+        assert_eq!(p.border, highlight);
+        assert_eq!(p.flash, highlight);
+    }
+
+    #[test]
+    fn colorblind_themes_pass_contrast_checks() {
+        for name in ["Deuteranopia", "Protanopia", "Tritanopia"] {
+            let t = theme::builtin().into_iter().find(|t| t.name == name).unwrap();
+            for chrome in light_chrome().into_iter().chain(dark_chrome()) {
+                let ratio = contrast_ratio(t.border_color, chrome);
+                assert!(ratio >= 3.0, "{} border on {:?}: {}", name, chrome, ratio);
+            }
+            let active = t.badge_color.unwrap_or(t.border_color);
+            assert!(contrast_ratio(active, t.badge_inactive_color) >= 3.0, "{} badges", name);
+            assert!(contrast_ratio(active, accent::WHITE) >= 3.0, "{} badge text", name);
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod shake;
+pub mod warp;
@@ -0,0 +1,146 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Distance (px) a stroke has to cover before its reversal counts.
+const MIN_STROKE: i32 = 80;
+/// Reversals within this window make a shake.
+const WINDOW: Duration = Duration::from_millis(800);
+const REVERSALS: usize = 4;
+/// Quiet period after a shake so one long shake triggers only once.
+const COOLDOWN: Duration = Duration::from_millis(1000);
+
+/// Recognizes a quick back-and-forth "shake" of the mouse from cursor
+/// position samples. Horizontal and vertical strokes both count.
+#[derive(Default)]
+pub struct ShakeDetector {
+    x: Axis,
+    y: Axis,
+    reversals: VecDeque<Instant>,
+    cooldown_until: Option<Instant>,
+}
+
+#[derive(Default)]
+struct Axis {
+    pos: Option<i32>,
+    /// Direction of the current stroke: -1, 0 (none yet) or 1.
+    dir: i32,
+    /// Distance covered by the current stroke.
+    stroke: i32,
+}
+
+impl ShakeDetector {
+    /// Feed one cursor sample. Returns true when the samples so far form
+    /// a shake.
+    pub fn push(&mut self, now: Instant, x: i32, y: i32) -> bool {
+        // Both axes must see the sample, so no short-circuit
+        let reversed = self.x.step(x) | self.y.step(y);
+        if reversed {
+            self.reversals.push_back(now);
+        }
+        while self
+            .reversals
+            .front()
+            .is_some_and(|&t| now.saturating_duration_since(t) > WINDOW)
+        {
+            self.reversals.pop_front();
+        }
+
+        if self.cooldown_until.is_some_and(|until| now < until) {
+            return false;
+        }
+        if self.reversals.len() >= REVERSALS {
+            self.reversals.clear();
+            self.cooldown_until = Some(now + COOLDOWN);
+            return true;
+        }
+        false
+    }
+}
+
+impl Axis {
+    /// Track one coordinate. Returns true when a long enough stroke turns
+    /// around.
+    fn step(&mut self, pos: i32) -> bool {
+        let Some(prev) = self.pos.replace(pos) else {
+            return false;
+        };
+        let delta = pos - prev;
+        if delta == 0 {
+            return false;
+        }
+        let dir = delta.signum();
+        if dir == self.dir {
+            self.stroke += delta.abs();
+            return false;
+        }
+        let reversed = self.dir != 0 && self.stroke >= MIN_STROKE;
+        self.dir = dir;
+        self.stroke = delta.abs();
+        reversed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed `points` spaced `step_ms` apart; returns how many shakes fired.
+    fn run(detector: &mut ShakeDetector, start: Instant, step_ms: u64, points: &[(i32, i32)]) -> usize {
+        points
+            .iter()
+            .enumerate()
+            .filter(|&(i, &(x, y))| {
+                detector.push(start + Duration::from_millis(i as u64 * step_ms), x, y)
+            })
+            .count()
+    }
+
+    /// Horizontal back-and-forth between 0 and `amplitude`, `strokes` times.
+    fn zigzag(amplitude: i32, strokes: usize) -> Vec<(i32, i32)> {
+        let mut points = vec![(0, 0)];
+        for i in 0..strokes {
+            let target = if i % 2 == 0 { amplitude } else { 0 };
+            points.push((target / 2, 0));
+            points.push((target, 0));
+        }
+        points
+    }
+
+    #[test]
+    fn quick_zigzag_is_a_shake() {
+        let mut detector = ShakeDetector::default();
+        assert_eq!(run(&mut detector, Instant::now(), 25, &zigzag(150, 6)), 1);
+    }
+
+    #[test]
+    fn vertical_zigzag_is_a_shake() {
+        let mut detector = ShakeDetector::default();
+        let points: Vec<_> = zigzag(150, 6).into_iter().map(|(x, y)| (y, x)).collect();
+        assert_eq!(run(&mut detector, Instant::now(), 25, &points), 1);
+    }
+
+    #[test]
+    fn steady_movement_is_not_a_shake() {
+        let mut detector = ShakeDetector::default();
+        let points: Vec<_> = (0..40).map(|i| (i * 30, i * 10)).collect();
+        assert_eq!(run(&mut detector, Instant::now(), 25, &points), 0);
+    }
+
+    #[test]
+    fn small_jitter_is_not_a_shake() {
+        let mut detector = ShakeDetector::default();
+        assert_eq!(run(&mut detector, Instant::now(), 25, &zigzag(20, 12)), 0);
+    }
+
+    #[test]
+    fn slow_back_and_forth_is_not_a_shake() {
+        let mut detector = ShakeDetector::default();
+        assert_eq!(run(&mut detector, Instant::now(), 400, &zigzag(150, 6)), 0);
+    }
+
+    #[test]
+    fn long_shake_fires_once() {
+        let mut detector = ShakeDetector::default();
+        assert_eq!(run(&mut detector, Instant::now(), 25, &zigzag(150, 12)), 1);
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicU32, Ordering};
+
+use windows::Win32::Foundation::{HINSTANCE, LPARAM, LRESULT, RECT, WPARAM};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::System::SystemInformation::GetTickCount;
+use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+use windows::Win32::UI::WindowsAndMessaging::{
+    CallNextHookEx, SetCursorPos, SetWindowsHookExW, UnhookWindowsHookEx, HHOOK, KBDLLHOOKSTRUCT,
+    WH_KEYBOARD_LL,
+};
+
+use crate::config::WarpTarget;
+
+/// Windows whose last cursor position is kept; older ones are forgotten.
+const CAPACITY: usize = 32;
+/// How recent the last input must be for a focus change to count as the
+/// user's doing rather than another program's.
+const SWITCH_INPUT_MS: u32 = 1000;
+/// How far apart the last key event and the last input may be stamped and
+/// still be the same input.
+const KEY_SLACK_MS: u32 = 50;
+
+/// Low-level keyboard hook while `watch_keyboard` is on, else 0.
+static KEYBOARD_HOOK: AtomicIsize = AtomicIsize::new(0);
+/// Tick of the last key down or up seen by the hook.
+static LAST_KEY: AtomicU32 = AtomicU32::new(0);
+static KEY_SEEN: AtomicBool = AtomicBool::new(false);
+
+/// Where the cursor last was inside recently focused windows, as offsets
+/// from each window's top-left corner so they survive moves.
+#[derive(Default)]
+pub struct CursorMemory {
+    /// Most recent first.
+    entries: VecDeque<(isize, (i32, i32))>,
+}
+
+impl CursorMemory {
+    /// Remember the cursor at (`x`, `y`) for `hwnd`, if it is inside the window.
+    pub fn remember(&mut self, hwnd: isize, window_rect: &RECT, x: i32, y: i32) {
+        if !contains(window_rect, x, y) {
+            return;
+        }
+        self.entries.retain(|&(h, _)| h != hwnd);
+        self.entries.push_front((hwnd, (x - window_rect.left, y - window_rect.top)));
+        self.entries.truncate(CAPACITY);
+    }
+
+    /// Offset from the window's top-left corner remembered for `hwnd`.
+    pub fn recall(&self, hwnd: isize) -> Option<(i32, i32)> {
+        self.entries.iter().find(|&&(h, _)| h == hwnd).map(|&(_, offset)| offset)
+    }
+}
+
+/// Screen point to warp to inside `window_rect`. A remembered offset that
+/// no longer fits (the window shrank) falls back to the center.
+pub fn warp_point(window_rect: &RECT, target: WarpTarget, remembered: Option<(i32, i32)>) -> (i32, i32) {
+    if target == WarpTarget::LastPosition {
+        if let Some((dx, dy)) = remembered {
+            let (x, y) = (window_rect.left + dx, window_rect.top + dy);
+            if contains(window_rect, x, y) {
+                return (x, y);
+            }
+        }
+    }
+    (
+        (window_rect.left + window_rect.right) / 2,
+        (window_rect.top + window_rect.bottom) / 2,
+    )
+}
+
+/// Whether `exe_name` is on the exclusion list. Case-insensitive, and the
+/// `.exe` suffix is optional on either side.
+pub fn is_excluded(exe_name: &str, exclude: &[String]) -> bool {
+    let name = strip_exe(exe_name);
+    if name.is_empty() {
+        return false;
+    }
+    exclude.iter().any(|e| strip_exe(e).eq_ignore_ascii_case(name))
+}
+
+/// Whether a focus change at tick `now` came from the keyboard: there was
+/// input within `SWITCH_INPUT_MS`, and that input was the last key event,
+/// not a mouse move or click. Focus taken by other programs (dialogs,
+/// notifications, launching apps) has no recent input. Ticks are
+/// `GetTickCount` milliseconds and may wrap.
+pub fn is_keyboard_switch(now: u32, last_input: u32, last_key: Option<u32>) -> bool {
+    if now.wrapping_sub(last_input) > SWITCH_INPUT_MS {
+        return false;
+    }
+    // Signed so a key stamped just after the input still counts
+    last_key.is_some_and(|key| (last_input.wrapping_sub(key) as i32) <= KEY_SLACK_MS as i32)
+}
+
+/// `is_keyboard_switch` for the focus change happening now. Never true
+/// while the keyboard isn't watched.
+pub fn switched_by_keyboard() -> bool {
+    let mut info = LASTINPUTINFO { cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32, dwTime: 0 };
+    if !unsafe { GetLastInputInfo(&mut info) }.as_bool() {
+        return false;
+    }
+    let now = unsafe { GetTickCount() };
+    let last_key = KEY_SEEN.load(Ordering::SeqCst).then(|| LAST_KEY.load(Ordering::SeqCst));
+    is_keyboard_switch(now, info.dwTime, last_key)
+}
+
+/// Install or remove the low-level keyboard hook that records when keys were
+/// last pressed, for `switched_by_keyboard`. The hook only keeps key times.
+pub fn watch_keyboard(enabled: bool) {
+    let current = KEYBOARD_HOOK.load(Ordering::SeqCst);
+    if enabled == (current != 0) {
+        return;
+    }
+    if enabled {
+        let module = unsafe { GetModuleHandleW(None) }.ok().map(HINSTANCE::from);
+        match unsafe { SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_proc), module, 0) } {
+            Ok(hook) => KEYBOARD_HOOK.store(hook.0 as isize, Ordering::SeqCst),
+            Err(e) => log::warn!("Failed to install keyboard hook: {}", e),
+        }
+    } else {
+        let _ = unsafe { UnhookWindowsHookEx(HHOOK(current as *mut _)) };
+        KEYBOARD_HOOK.store(0, Ordering::SeqCst);
+        KEY_SEEN.store(false, Ordering::SeqCst);
+    }
+}
+
+unsafe extern "system" fn keyboard_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code >= 0 {
+        let event = unsafe { &*(lparam.0 as *const KBDLLHOOKSTRUCT) };
+        LAST_KEY.store(event.time, Ordering::SeqCst);
+        KEY_SEEN.store(true, Ordering::SeqCst);
+    }
+    unsafe { CallNextHookEx(None, code, wparam, lparam) }
+}
+
+pub fn move_cursor(x: i32, y: i32) {
+    unsafe {
+        let _ = SetCursorPos(x, y);
+    }
+}
+
+fn strip_exe(name: &str) -> &str {
+    let name = name.trim();
+    match name.len().checked_sub(4) {
+        Some(cut) if name.is_char_boundary(cut) && name[cut..].eq_ignore_ascii_case(".exe") => &name[..cut],
+        _ => name,
+    }
+}
+
+fn contains(rect: &RECT, x: i32, y: i32) -> bool {
+    x >= rect.left && x < rect.right && y >= rect.top && y < rect.bottom
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> RECT {
+        RECT { left, top, right, bottom }
+    }
+
+    #[test]
+    fn recent_key_press_is_a_keyboard_switch() {
+        assert!(is_keyboard_switch(10_000, 9_900, Some(9_900)));
+        // The hook and the last input tick can be stamped a little apart
+        assert!(is_keyboard_switch(10_000, 9_900, Some(9_880)));
+        assert!(is_keyboard_switch(10_000, 9_900, Some(9_905)));
+    }
+
+    #[test]
+    fn focus_change_without_recent_input_is_not() {
+        // A dialog or app launch grabbing focus while the user is idle
+        assert!(!is_keyboard_switch(10_000, 5_000, Some(5_000)));
+        assert!(!is_keyboard_switch(10_000, 8_999, Some(8_999)));
+    }
+
+    #[test]
+    fn click_after_pause_is_not() {
+        // Moved to another monitor's window, paused, then clicked
+        assert!(!is_keyboard_switch(10_000, 9_900, Some(2_000)));
+        assert!(!is_keyboard_switch(10_000, 9_900, Some(9_700)));
+    }
+
+    #[test]
+    fn no_key_seen_is_not() {
+        assert!(!is_keyboard_switch(10_000, 9_900, None));
+    }
+
+    #[test]
+    fn tick_wraparound() {
+        assert!(is_keyboard_switch(100, u32::MAX - 100, Some(u32::MAX - 100)));
+        assert!(is_keyboard_switch(100, 20, Some(u32::MAX - 10)));
+        assert!(!is_keyboard_switch(100, u32::MAX - 100, Some(u32::MAX - 5_000)));
+    }
+
+    #[test]
+    fn center_of_window() {
+        let window = rect(1920, 100, 2920, 700);
+        assert_eq!(warp_point(&window, WarpTarget::Center, Some((10, 10))), (2420, 400));
+    }
+
+    #[test]
+    fn last_position_follows_moved_window() {
+        let mut memory = CursorMemory::default();
+        memory.remember(7, &rect(0, 0, 800, 600), 100, 50);
+        let moved = rect(1920, 200, 2720, 800);
+        let offset = memory.recall(7);
+        assert_eq!(warp_point(&moved, WarpTarget::LastPosition, offset), (2020, 250));
+    }
+
+    #[test]
+    fn offset_outside_shrunk_window_falls_back_to_center() {
+        let shrunk = rect(0, 0, 200, 100);
+        assert_eq!(warp_point(&shrunk, WarpTarget::LastPosition, Some((500, 50))), (100, 50));
+        assert_eq!(warp_point(&shrunk, WarpTarget::LastPosition, None), (100, 50));
+    }
+
+    #[test]
+    fn cursor_outside_window_is_not_remembered() {
+        let mut memory = CursorMemory::default();
+        memory.remember(7, &rect(0, 0, 800, 600), 900, 50);
+        assert_eq!(memory.recall(7), None);
+    }
+
+    #[test]
+    fn newest_position_wins_and_oldest_is_evicted() {
+        let mut memory = CursorMemory::default();
+        let window = rect(0, 0, 800, 600);
+        memory.remember(1, &window, 10, 10);
+        memory.remember(1, &window, 20, 20);
+        assert_eq!(memory.recall(1), Some((20, 20)));
+
+        for hwnd in 2..=CAPACITY as isize + 1 {
+            memory.remember(hwnd, &window, 5, 5);
+        }
+        assert_eq!(memory.recall(1), None);
+        assert_eq!(memory.recall(2), Some((5, 5)));
+    }
+
+    #[test]
+    fn exclusion_ignores_case_and_exe_suffix() {
+        let exclude = vec!["Game.exe".to_string(), "obs64".to_string()];
+        assert!(is_excluded("game.EXE", &exclude));
+        assert!(is_excluded("game", &exclude));
+        assert!(is_excluded("obs64.exe", &exclude));
+        assert!(!is_excluded("notepad.exe", &exclude));
+        assert!(!is_excluded("", &exclude));
+    }
+}
@@ -21,10 +21,6 @@ pub fn set_msg_hwnd(hwnd: HWND) {
     MSG_HWND.store(hwnd.0 as isize, Ordering::SeqCst);
 }
 
-pub fn msg_hwnd_value() -> isize {
-    MSG_HWND.load(Ordering::SeqCst)
-}
-
 /// Install SetWinEventHook for EVENT_SYSTEM_FOREGROUND and EVENT_OBJECT_LOCATIONCHANGE.
 /// Returns hook handles that must be unhooked on exit.
 pub fn install_hooks() -> (HWINEVENTHOOK, HWINEVENTHOOK) {
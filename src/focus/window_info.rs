@@ -1,8 +1,16 @@
-use windows::Win32::Foundation::{HWND, RECT};
-use windows::Win32::Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_EXTENDED_FRAME_BOUNDS};
+use windows::Win32::Foundation::{HWND, LPARAM, RECT};
+use windows::Win32::Graphics::Dwm::{
+    DwmGetWindowAttribute, DWMWA_CLOAKED, DWMWA_EXTENDED_FRAME_BOUNDS,
+    DWMWA_WINDOW_CORNER_PREFERENCE, DWMWCP_DONOTROUND, DWMWCP_ROUNDSMALL,
+    DWM_WINDOW_CORNER_PREFERENCE,
+};
+use windows::Win32::Graphics::Gdi::{GetMonitorInfoW, MonitorFromWindow, MONITORINFO, MONITOR_DEFAULTTONEAREST};
+use windows::Win32::UI::HiDpi::GetDpiForWindow;
 use windows::Win32::UI::WindowsAndMessaging::{
-    GetForegroundWindow, GetWindowTextLengthW, GetWindowTextW, GetWindowThreadProcessId,
-    IsWindowVisible,
+    EnumWindows, GetForegroundWindow, GetWindow, GetWindowLongW, GetWindowPlacement,
+    GetWindowTextLengthW, GetWindowTextW, GetWindowThreadProcessId, IsIconic, IsWindowVisible,
+    IsZoomed, SetForegroundWindow, ShowWindow, GWL_EXSTYLE, GWL_STYLE, GW_OWNER, SW_RESTORE,
+    WINDOWPLACEMENT, WS_CAPTION, WS_EX_TOOLWINDOW,
 };
 use windows::Win32::System::Threading::{
     OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION, QueryFullProcessImageNameW,
@@ -10,6 +18,8 @@ use windows::Win32::System::Threading::{
 };
 use windows::Win32::Foundation::CloseHandle;
 
+use crate::monitor::geometry;
+
 /// Snapshot of a window's properties at a point in time.
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -47,6 +57,119 @@ pub fn get_foreground_window_info() -> Option<WindowSnapshot> {
     }
 }
 
+/// Visible, unowned top-level app windows in z-order (topmost first), the
+/// same set Alt+Tab would show. Minimized and cloaked windows are skipped.
+pub fn enumerate_app_windows() -> Vec<WindowSnapshot> {
+    enumerate_windows(false)
+}
+
+/// Like `enumerate_app_windows`, but minimized windows are included with
+/// the rect they will restore to, so they can be switched to.
+pub fn enumerate_switch_targets() -> Vec<WindowSnapshot> {
+    enumerate_windows(true)
+}
+
+struct EnumContext {
+    windows: Vec<WindowSnapshot>,
+    include_minimized: bool,
+}
+
+fn enumerate_windows(include_minimized: bool) -> Vec<WindowSnapshot> {
+    let mut ctx = EnumContext {
+        windows: Vec::new(),
+        include_minimized,
+    };
+    unsafe {
+        let _ = EnumWindows(
+            Some(enum_window_callback),
+            LPARAM(&mut ctx as *mut EnumContext as isize),
+        );
+    }
+    ctx.windows
+}
+
+unsafe extern "system" fn enum_window_callback(
+    hwnd: HWND,
+    lparam: LPARAM,
+) -> windows::core::BOOL {
+    let ctx = &mut *(lparam.0 as *mut EnumContext);
+    let windows = &mut ctx.windows;
+
+    let minimized = IsIconic(hwnd).as_bool();
+    let eligible = is_app_window(hwnd) && (ctx.include_minimized || !minimized);
+    // Untitled windows are helper surfaces rather than apps
+    let title = if eligible { get_window_title(hwnd) } else { String::new() };
+    if !title.is_empty() {
+        // A minimized window's frame sits off-screen; use its restore position
+        let rect = if minimized { get_restore_rect(hwnd) } else { get_extended_frame_bounds(hwnd) };
+        if let Some(rect) = rect {
+            windows.push(WindowSnapshot {
+                hwnd: hwnd.0 as isize,
+                title,
+                exe_name: get_exe_name(hwnd),
+                rect,
+                is_visible: true,
+            });
+        }
+    }
+
+    windows::core::BOOL(1) // TRUE - continue enumeration
+}
+
+/// Whether a top-level window is one the user would consider an app window.
+fn is_app_window(hwnd: HWND) -> bool {
+    unsafe {
+        if !IsWindowVisible(hwnd).as_bool() {
+            return false;
+        }
+        if GetWindow(hwnd, GW_OWNER).is_ok_and(|owner| !owner.0.is_null()) {
+            return false;
+        }
+        let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE) as u32;
+        if ex_style & WS_EX_TOOLWINDOW.0 != 0 {
+            return false;
+        }
+        // Suspended UWP apps and windows on other virtual desktops are cloaked
+        let mut cloaked = 0u32;
+        let hr = DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_CLOAKED,
+            &mut cloaked as *mut u32 as *mut _,
+            std::mem::size_of::<u32>() as u32,
+        );
+        !(hr.is_ok() && cloaked != 0)
+    }
+}
+
+/// Where a minimized window will reappear when restored, in screen coordinates.
+fn get_restore_rect(hwnd: HWND) -> Option<RECT> {
+    let mut placement = WINDOWPLACEMENT {
+        length: std::mem::size_of::<WINDOWPLACEMENT>() as u32,
+        ..Default::default()
+    };
+    unsafe { GetWindowPlacement(hwnd, &mut placement).ok()? };
+
+    // For a minimized window this is the monitor it restores onto
+    let mut info = MONITORINFO { cbSize: std::mem::size_of::<MONITORINFO>() as u32, ..Default::default() };
+    let monitor = unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST) };
+    if !unsafe { GetMonitorInfoW(monitor, &mut info) }.as_bool() {
+        return Some(placement.rcNormalPosition);
+    }
+    Some(geometry::workspace_to_screen(&placement.rcNormalPosition, &info.rcMonitor, &info.rcWork))
+}
+
+/// Bring a window to the foreground, restoring it first if minimized.
+/// Only succeeds while the calling process owns the foreground.
+pub fn activate_window(hwnd: isize) -> bool {
+    let hwnd = HWND(hwnd as *mut _);
+    unsafe {
+        if IsIconic(hwnd).as_bool() {
+            let _ = ShowWindow(hwnd, SW_RESTORE);
+        }
+        SetForegroundWindow(hwnd).as_bool()
+    }
+}
+
 /// Get the extended frame bounds (excludes invisible Win10/11 borders).
 pub fn get_extended_frame_bounds(hwnd: HWND) -> Option<RECT> {
     unsafe {
@@ -65,6 +188,49 @@ pub fn get_extended_frame_bounds(hwnd: HWND) -> Option<RECT> {
     }
 }
 
+/// Corner radius (in px at 96 DPI) DWM draws for this window.
+/// Returns 0 for windows that opted out of rounding, and on Windows 10
+/// where DWMWA_WINDOW_CORNER_PREFERENCE is not supported.
+pub fn get_system_corner_radius(hwnd: HWND) -> f32 {
+    unsafe {
+        let mut pref = DWM_WINDOW_CORNER_PREFERENCE::default();
+        let hr = DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_WINDOW_CORNER_PREFERENCE,
+            &mut pref as *mut DWM_WINDOW_CORNER_PREFERENCE as *mut _,
+            std::mem::size_of::<DWM_WINDOW_CORNER_PREFERENCE>() as u32,
+        );
+        if hr.is_err() {
+            return 0.0;
+        }
+        match pref {
+            DWMWCP_DONOTROUND => 0.0,
+            DWMWCP_ROUNDSMALL => 4.0,
+            _ => 8.0, // DWMWCP_DEFAULT / DWMWCP_ROUND
+        }
+    }
+}
+
+/// Scale factor of the window's monitor relative to 96 DPI.
+pub fn get_dpi_scale(hwnd: HWND) -> f32 {
+    let dpi = unsafe { GetDpiForWindow(hwnd) };
+    if dpi == 0 {
+        1.0
+    } else {
+        dpi as f32 / 96.0
+    }
+}
+
+pub fn is_maximized(hwnd: HWND) -> bool {
+    unsafe { IsZoomed(hwnd).as_bool() }
+}
+
+/// Whether the window has a title bar; fullscreen games and videos drop it.
+pub fn has_caption(hwnd: HWND) -> bool {
+    let style = unsafe { GetWindowLongW(hwnd, GWL_STYLE) } as u32;
+    style & WS_CAPTION.0 == WS_CAPTION.0
+}
+
 fn get_window_title(hwnd: HWND) -> String {
     unsafe {
         let len = GetWindowTextLengthW(hwnd);
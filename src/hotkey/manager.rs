@@ -0,0 +1,146 @@
+use global_hotkey::{
+    GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState,
+    hotkey::{Code, HotKey, Modifiers},
+};
+
+/// What a registered global hotkey does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyAction {
+    /// Ctrl+Shift+F, held: identify cards on every monitor.
+    Reveal,
+    /// Ctrl+Alt+Space: fuzzy window picker.
+    Picker,
+    /// Ctrl+Alt+M: ripple around the mouse cursor.
+    Locate,
+    /// Ctrl+Alt+J: move the cursor to the focused window.
+    Warp,
+}
+
+/// Owns every global hotkey. They share one event channel, so a single
+/// manager has to poll it and dispatch by id.
+pub struct HotkeyManager {
+    manager: GlobalHotKeyManager,
+    bindings: Vec<(HotKey, HotkeyAction)>,
+    /// Reveal is held rather than tapped; auto-repeat is ignored while set.
+    pub reveal_active: bool,
+}
+
+impl HotkeyManager {
+    pub fn new() -> Option<Self> {
+        let manager = GlobalHotKeyManager::new().ok()?;
+        let mut hotkeys = Self {
+            manager,
+            bindings: Vec::new(),
+            reveal_active: false,
+        };
+        hotkeys.register(
+            HotKey::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::KeyF),
+            HotkeyAction::Reveal,
+            "Ctrl+Shift+F",
+        );
+        hotkeys.register(
+            HotKey::new(Some(Modifiers::CONTROL | Modifiers::ALT), Code::Space),
+            HotkeyAction::Picker,
+            "Ctrl+Alt+Space",
+        );
+        hotkeys.register(
+            HotKey::new(Some(Modifiers::CONTROL | Modifiers::ALT), Code::KeyM),
+            HotkeyAction::Locate,
+            "Ctrl+Alt+M",
+        );
+        hotkeys.register(
+            HotKey::new(Some(Modifiers::CONTROL | Modifiers::ALT), Code::KeyJ),
+            HotkeyAction::Warp,
+            "Ctrl+Alt+J",
+        );
+        Some(hotkeys)
+    }
+
+    /// Register one hotkey. A combination already taken by another app is
+    /// logged and skipped so the remaining hotkeys still work.
+    fn register(&mut self, hotkey: HotKey, action: HotkeyAction, name: &str) {
+        match self.manager.register(hotkey) {
+            Ok(()) => {
+                log::info!("Registered global hotkey: {}", name);
+                self.bindings.push((hotkey, action));
+            }
+            Err(e) => log::warn!("Failed to register hotkey {}: {}", name, e),
+        }
+    }
+
+    /// Drain pending hotkey events and return the actions that fired.
+    pub fn poll(&mut self) -> Vec<HotkeyAction> {
+        let mut fired = Vec::new();
+        while let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
+            fired.extend(dispatch(&self.bindings, &mut self.reveal_active, &event));
+        }
+        fired
+    }
+}
+
+/// The action `event` fires, if any. Only presses count: every press is
+/// followed by a release event for the same hotkey.
+fn dispatch(
+    bindings: &[(HotKey, HotkeyAction)],
+    reveal_active: &mut bool,
+    event: &GlobalHotKeyEvent,
+) -> Option<HotkeyAction> {
+    if event.state() != HotKeyState::Pressed {
+        return None;
+    }
+    let &(_, action) = bindings.iter().find(|(hk, _)| hk.id() == event.id())?;
+    if action == HotkeyAction::Reveal {
+        if *reveal_active {
+            return None;
+        }
+        *reveal_active = true;
+    }
+    Some(action)
+}
+
+impl Drop for HotkeyManager {
+    fn drop(&mut self) {
+        for (hotkey, _) in &self.bindings {
+            let _ = self.manager.unregister(*hotkey);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bindings() -> Vec<(HotKey, HotkeyAction)> {
+        vec![
+            (HotKey::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::KeyF), HotkeyAction::Reveal),
+            (HotKey::new(Some(Modifiers::CONTROL | Modifiers::ALT), Code::KeyM), HotkeyAction::Locate),
+        ]
+    }
+
+    fn tap(hotkey: &HotKey) -> [GlobalHotKeyEvent; 2] {
+        [HotKeyState::Pressed, HotKeyState::Released]
+            .map(|state| GlobalHotKeyEvent { id: hotkey.id(), state })
+    }
+
+    fn fired(events: &[GlobalHotKeyEvent], reveal_active: &mut bool) -> Vec<HotkeyAction> {
+        let bindings = bindings();
+        events.iter().filter_map(|e| dispatch(&bindings, reveal_active, e)).collect()
+    }
+
+    #[test]
+    fn tap_fires_once() {
+        let locate = bindings()[1].0;
+        assert_eq!(fired(&tap(&locate), &mut false), vec![HotkeyAction::Locate]);
+    }
+
+    #[test]
+    fn reveal_release_does_not_fire_again() {
+        let reveal = bindings()[0].0;
+        // Ctrl let go before F clears the held state before the release arrives
+        let mut reveal_active = false;
+        let [press, release] = tap(&reveal);
+        assert_eq!(fired(&[press], &mut reveal_active), vec![HotkeyAction::Reveal]);
+        reveal_active = false;
+        assert!(fired(&[release], &mut reveal_active).is_empty());
+    }
+}
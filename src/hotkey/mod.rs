@@ -1 +1 @@
-pub mod reveal;
+pub mod manager;
@@ -1,36 +1,126 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod accent;
 mod app;
+mod color;
 mod config;
+mod contrast;
+mod cursor;
 mod focus;
 mod hotkey;
 mod monitor;
 mod overlay;
+mod picker;
 mod settings;
+mod theme;
 mod tray;
 
+use std::time::Instant;
+
 use windows::core::PCWSTR;
-use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, RECT, WPARAM};
+use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, POINT, RECT, WPARAM};
 use windows::Win32::System::LibraryLoader::GetModuleHandleW;
 use windows::Win32::UI::Input::KeyboardAndMouse::GetAsyncKeyState;
 use windows::Win32::UI::WindowsAndMessaging::*;
 
 use app::{App, FocusState};
+use config::{BadgeStyle, Config, CornerStyle, FlashTarget, HighContrast, IndicatorMode};
+use cursor::shake::ShakeDetector;
+use cursor::warp;
 use focus::tracker::{self, WM_FOCUS_CHANGED, WM_LOCATION_CHANGED};
 use focus::window_info;
-use monitor::{enumeration, geometry};
+use hotkey::manager::{HotkeyAction, HotkeyManager};
+use monitor::enumeration::{self, MonitorInfo};
+use monitor::geometry;
 use overlay::border::BorderOverlay;
 use overlay::flash::FlashOverlay;
-use overlay::indicator::MonitorIndicators;
+use overlay::indicator::{self, BadgeFields, MonitorIndicators};
+use overlay::locator::CursorLocator;
+use overlay::minimap::MiniMap;
+use overlay::reveal::{CardInfo, CardWindow, RevealOverlay};
+use overlay::spotlight::SpotlightOverlay;
+use overlay::title_tab::TitleTab;
+use theme::ThemeColors;
 use tray::icon::{
     self as tray_icon_mod, SystemTray, MENU_BORDER_STYLE, MENU_QUIT, MENU_SETTINGS,
-    MENU_TOGGLE_BORDER, MENU_TOGGLE_FLASH, MENU_TOGGLE_INDICATOR,
+    MENU_THEME_PREFIX, MENU_TOGGLE_BORDER, MENU_TOGGLE_FLASH, MENU_TOGGLE_INDICATOR, MENU_TOGGLE_SPOTLIGHT,
 };
 
 const TIMER_POLL: usize = 1;
-const TIMER_FLASH_HIDE: usize = 2;
 const TIMER_HOTKEY_CHECK: usize = 3;
 const TIMER_SETTINGS_POLL: usize = 4;
+const TIMER_ANIMATION: usize = 5;
+const TIMER_PICKER_POLL: usize = 6;
+const TIMER_CURSOR_POLL: usize = 7;
+const TIMER_SYSTEM_COLORS_POLL: usize = 8;
+
+/// Frame interval for overlay animations (~60fps).
+const ANIMATION_FRAME_MS: u32 = 16;
+/// Cursor sampling interval while a locator trigger is enabled; shake
+/// detection needs several samples per stroke.
+const CURSOR_POLL_MS: u32 = 25;
+/// How often the accent color, app mode and high-contrast setting are
+/// re-read while the colors follow them. The message-only window gets no
+/// setting broadcasts.
+const SYSTEM_COLORS_POLL_MS: u32 = 1000;
+
+/// Opacity multiplier for the flash on focus changes within a monitor.
+const SUBTLE_FLASH_STRENGTH: f32 = 0.4;
+
+/// Overlay windows driven by the message loop; each is None if creation failed.
+struct Overlays {
+    border: Option<BorderOverlay>,
+    flash: Option<FlashOverlay>,
+    indicators: Option<MonitorIndicators>,
+    locator: Option<CursorLocator>,
+    minimap: Option<MiniMap>,
+    reveal: Option<RevealOverlay>,
+    spotlight: Option<SpotlightOverlay>,
+    title_tab: Option<TitleTab>,
+}
+
+impl Overlays {
+    /// Whether `hwnd` is one of our own overlay windows.
+    fn owns(&self, hwnd: isize) -> bool {
+        self.border.as_ref().is_some_and(|bo| bo.hwnd.0 as isize == hwnd)
+            || self.flash.as_ref().is_some_and(|fo| fo.hwnd.0 as isize == hwnd)
+            || self.locator.as_ref().is_some_and(|lc| lc.hwnd.0 as isize == hwnd)
+            || self.spotlight.as_ref().is_some_and(|sp| sp.hwnd.0 as isize == hwnd)
+            || self.title_tab.as_ref().is_some_and(|tab| tab.hwnd.0 as isize == hwnd)
+            || self
+                .indicators
+                .as_ref()
+                .is_some_and(|ind| ind.hwnd_list().contains(&hwnd))
+            || self
+                .minimap
+                .as_ref()
+                .is_some_and(|mm| mm.hwnd_list().contains(&hwnd))
+            || self
+                .reveal
+                .as_ref()
+                .is_some_and(|rv| rv.hwnd_list().contains(&hwnd))
+    }
+
+    /// Show the badges or the mini-map, whichever the indicator mode selects.
+    fn apply_indicator_visibility(&mut self, config: &Config) {
+        let badges = config.indicator_enabled && config.indicator_mode == IndicatorMode::Badges;
+        let minimap = config.indicator_enabled && config.indicator_mode == IndicatorMode::MiniMap;
+        if let Some(ref ind) = self.indicators {
+            if badges {
+                ind.show_all();
+            } else {
+                ind.hide_all();
+            }
+        }
+        if let Some(ref mut mm) = self.minimap {
+            if minimap {
+                mm.show();
+            } else {
+                mm.hide();
+            }
+        }
+    }
+}
 
 fn main() {
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
@@ -41,10 +131,14 @@ fn main() {
         let code = settings::launch::run_settings_main();
         std::process::exit(code);
     }
+    if std::env::args().any(|a| a == "--picker") {
+        let code = picker::launch::run_picker_main();
+        std::process::exit(code);
+    }
 
     log::info!("whereismywindow starting");
 
-    let config = settings::persistence::load_config();
+    let (config, theme_colors) = settings::persistence::load_config_with_theme_colors();
 
     // Enumerate monitors
     let monitors = enumeration::enumerate_monitors();
@@ -52,17 +146,45 @@ fn main() {
         log::info!("{}", enumeration::format_monitor(m, i));
     }
 
-    let mut app = App::new(config.clone());
+    let mut app = App::new(config.clone(), theme_colors);
     app.monitors = monitors;
 
-    // Create overlays
+    // Create overlays — the spotlight first so it sits below the others
+    let spotlight = SpotlightOverlay::new(config.spotlight_opacity);
     let mut border_overlay = BorderOverlay::new(config.border_color, config.border_thickness, config.border_style);
-    let flash_overlay = FlashOverlay::new(config.flash_opacity);
+    if let Some(ref mut bo) = border_overlay {
+        bo.set_gradient(
+            &config.gradient_stops,
+            config.gradient_rotate,
+            config.gradient_rotation_ms,
+        );
+        bo.set_dash_pattern(config.dash_length, config.dash_gap);
+        bo.set_glow(config.glow_width, config.glow_layers, config.glow_falloff);
+        bo.set_render_mode(config.render_mode);
+        bo.set_animation(
+            config.animate_transitions,
+            config.animation_duration_ms,
+            config.animation_easing,
+        );
+    }
+    let mut flash_overlay = FlashOverlay::new(config.flash_opacity, config.render_mode);
+    if let Some(ref mut fo) = flash_overlay {
+        fo.set_color(&config.effective_flash_color());
+        fo.set_shape(config.flash_shape);
+        fo.set_duration(config.flash_duration_ms, config.flash_fade_ms);
+    }
+    let title_tab = TitleTab::new(&config.border_color, config.render_mode);
 
-    // Create monitor indicators (bottom-left corner badges)
-    // Use full_rect to position at absolute screen bottom (badges are TOPMOST so visible over taskbar)
+    // Create monitor indicators (corner badges)
+    // Use full_rect to position against the screen edges (badges are TOPMOST so visible over taskbar)
     let monitor_rects: Vec<_> = app.monitors.iter().map(|m| m.full_rect).collect();
-    let mut indicators = MonitorIndicators::new(&monitor_rects, &config.border_color);
+    let indicators = MonitorIndicators::new(
+        &monitor_rects,
+        &badge_styles(&config, &app.monitors),
+        &config.effective_badge_color(),
+        &config.badge_inactive_color,
+        config.render_mode,
+    );
 
     if border_overlay.is_none() {
         log::warn!("Failed to create border overlay");
@@ -70,17 +192,50 @@ fn main() {
     if flash_overlay.is_none() {
         log::warn!("Failed to create flash overlay");
     }
+    if spotlight.is_none() {
+        log::warn!("Failed to create spotlight overlay");
+    }
+    if title_tab.is_none() {
+        log::warn!("Failed to create title tab overlay");
+    }
+    let minimap = MiniMap::new(
+        &monitor_rects,
+        &badge_styles(&config, &app.monitors),
+        config.minimap_width,
+        &config.effective_badge_color(),
+        config.render_mode,
+        config.minimap_scope,
+    );
+
     if indicators.is_none() {
         log::warn!("Failed to create monitor indicators");
     } else {
         log::info!("Monitor indicators created for {} monitors", monitor_rects.len());
-        // Show all badges initially if enabled
-        if config.indicator_enabled {
-            if let Some(ref ind) = indicators {
-                ind.show_all();
-            }
-        }
     }
+    if minimap.is_none() {
+        log::warn!("Failed to create mini-map");
+    }
+    let locator = CursorLocator::new(&config.border_color, config.render_mode);
+    if locator.is_none() {
+        log::warn!("Failed to create cursor locator");
+    }
+    let reveal = RevealOverlay::new(&monitor_rects, &config.border_color, config.render_mode);
+    if reveal.is_none() {
+        log::warn!("Failed to create reveal overlay");
+    }
+
+    let mut overlays = Overlays {
+        border: border_overlay,
+        flash: flash_overlay,
+        indicators,
+        locator,
+        minimap,
+        reveal,
+        spotlight,
+        title_tab,
+    };
+    // Show the badges or the mini-map initially if enabled
+    overlays.apply_indicator_visibility(&config);
 
     // Create system tray
     let tray = SystemTray::new(&config);
@@ -89,9 +244,9 @@ fn main() {
     }
 
     // Create hotkey handler
-    let hotkey_handler = hotkey::reveal::RevealHotkey::new();
+    let mut hotkey_handler = HotkeyManager::new();
     if hotkey_handler.is_none() {
-        log::warn!("Failed to register global hotkey");
+        log::warn!("Failed to create global hotkey manager");
     }
 
     // Create message-only window for receiving events
@@ -105,18 +260,28 @@ fn main() {
     // Install event hooks
     let (focus_hook, location_hook) = tracker::install_hooks();
     log::info!("Event hooks installed");
+    warp::watch_keyboard(app.config.warp_on_monitor_change);
 
     // Set up a poll timer for position tracking (~60fps)
     unsafe {
         SetTimer(Some(msg_hwnd), TIMER_POLL, config.poll_interval_ms, None);
         SetTimer(Some(msg_hwnd), TIMER_HOTKEY_CHECK, 50, None);
     }
+    update_cursor_timer(&app.config, msg_hwnd);
+    update_system_colors_timer(&app.config, msg_hwnd);
 
     // Do an initial focus check
-    update_focus_state(&mut app, &mut border_overlay, &flash_overlay, &mut indicators);
+    update_focus_state(&mut app, &mut overlays);
 
     // Settings channel (populated when settings window is opened)
     let mut settings_child: Option<std::process::Child> = None;
+    let mut picker_child: Option<std::process::Child> = None;
+
+    // Cursor samples for the shake and monitor-change locator triggers
+    let mut cursor_watch = CursorWatch::default();
+
+    // Frame timer runs only while an overlay is animating
+    let mut animation_timer_active = false;
 
     // Message loop
     log::info!("Entering message loop");
@@ -130,15 +295,10 @@ fn main() {
 
             match msg.message {
                 WM_FOCUS_CHANGED => {
-                    update_focus_state(
-                        &mut app,
-                        &mut border_overlay,
-                        &flash_overlay,
-                        &mut indicators,
-                    );
+                    update_focus_state(&mut app, &mut overlays);
                 }
                 WM_LOCATION_CHANGED => {
-                    if app.config.border_enabled {
+                    if follows_window_moves(&app.config) {
                         if let Some(ref focus) = app.focus {
                             let fg = GetForegroundWindow();
                             if fg.0 as isize == focus.hwnd {
@@ -146,8 +306,29 @@ fn main() {
                                     HWND(focus.hwnd as *mut _),
                                 ) {
                                     let clamped = clamp_to_monitor(&new_rect, &focus.monitor_rect);
-                                    if let Some(ref mut bo) = border_overlay {
-                                        bo.update(&clamped);
+                                    let radius = border_corner_radius(
+                                        &app,
+                                        focus.hwnd,
+                                        &new_rect,
+                                        focus.monitor_index,
+                                    );
+                                    update_spotlight(
+                                        &app,
+                                        &mut overlays.spotlight,
+                                        focus.hwnd,
+                                        &new_rect,
+                                        &focus.monitor_rect,
+                                        radius,
+                                    );
+                                    if app.config.border_enabled {
+                                        if let Some(ref mut bo) = overlays.border {
+                                            bo.set_corner_radius(radius);
+                                            bo.update(&clamped);
+                                        }
+                                    }
+                                    update_title_tab(&app, &mut overlays, &new_rect);
+                                    if let Some(ref mut mm) = overlays.minimap {
+                                        mm.set_focus(focus.monitor_index, Some(&new_rect));
                                     }
                                 }
                             }
@@ -158,7 +339,7 @@ fn main() {
                     let timer_id = msg.wParam.0;
                     match timer_id {
                         TIMER_POLL => {
-                            if app.config.border_enabled {
+                            if follows_window_moves(&app.config) {
                                 if let Some(ref focus) = app.focus {
                                     let fg = GetForegroundWindow();
                                     if fg.0 as isize == focus.hwnd {
@@ -167,54 +348,136 @@ fn main() {
                                         ) {
                                             let clamped = clamp_to_monitor(&new_rect, &focus.monitor_rect);
                                             if clamped != focus.window_rect {
-                                                if let Some(ref mut bo) = border_overlay {
-                                                    bo.update(&clamped);
+                                                let radius = border_corner_radius(
+                                                    &app,
+                                                    focus.hwnd,
+                                                    &new_rect,
+                                                    focus.monitor_index,
+                                                );
+                                                update_spotlight(
+                                                    &app,
+                                                    &mut overlays.spotlight,
+                                                    focus.hwnd,
+                                                    &new_rect,
+                                                    &focus.monitor_rect,
+                                                    radius,
+                                                );
+                                                if app.config.border_enabled {
+                                                    if let Some(ref mut bo) = overlays.border {
+                                                        bo.set_corner_radius(radius);
+                                                        bo.update(&clamped);
+                                                    }
+                                                }
+                                                update_title_tab(&app, &mut overlays, &new_rect);
+                                                if let Some(ref mut mm) = overlays.minimap {
+                                                    mm.set_focus(focus.monitor_index, Some(&new_rect));
                                                 }
                                             }
                                         }
                                     } else {
                                         // Foreground changed away from tracked window —
                                         // hide border until next WM_FOCUS_CHANGED updates it
-                                        if let Some(ref bo) = border_overlay {
+                                        if let Some(ref bo) = overlays.border {
                                             bo.hide();
                                         }
+                                        if let Some(ref sp) = overlays.spotlight {
+                                            sp.hide();
+                                        }
+                                        if let Some(ref tab) = overlays.title_tab {
+                                            tab.hide();
+                                        }
                                     }
                                 }
                             }
                         }
-                        TIMER_FLASH_HIDE => {
-                            if let Some(ref fo) = flash_overlay {
-                                fo.hide();
-                            }
-                            KillTimer(Some(msg_hwnd), TIMER_FLASH_HIDE).ok();
-                        }
                         TIMER_HOTKEY_CHECK => {
-                            if let Some(ref _hk) = hotkey_handler {
-                                if _hk.poll() {
-                                    log::info!("Hotkey reveal triggered");
-                                    show_reveal_info(&app);
+                            let fired = match hotkey_handler {
+                                Some(ref mut hk) => hk.poll(),
+                                None => Vec::new(),
+                            };
+                            for action in fired {
+                                match action {
+                                    HotkeyAction::Reveal if app.config.reveal_hotkey_enabled => {
+                                        log::info!("Hotkey reveal triggered");
+                                        show_reveal_info(&app);
+                                        if let Some(ref mut rv) = overlays.reveal {
+                                            rv.show(&reveal_cards(&app));
+                                        }
+                                    }
+                                    HotkeyAction::Locate if app.config.locator_hotkey_enabled => {
+                                        locate_cursor(&app, &mut overlays);
+                                    }
+                                    HotkeyAction::Warp if app.config.warp_hotkey_enabled => {
+                                        if let Some(ref focus) = app.focus {
+                                            warp_cursor_to(&app, focus);
+                                        }
+                                    }
+                                    HotkeyAction::Picker
+                                        if app.config.picker_hotkey_enabled && picker_child.is_none() =>
+                                    {
+                                        if let Some(child) = picker::launch::open_picker() {
+                                            picker_child = Some(child);
+                                            SetTimer(Some(msg_hwnd), TIMER_PICKER_POLL, 100, None);
+                                        }
+                                    }
+                                    _ => {}
                                 }
                             }
 
                             let ctrl_down = GetAsyncKeyState(0x11) < 0;
                             let shift_down = GetAsyncKeyState(0x10) < 0;
                             let f_down = GetAsyncKeyState(0x46) < 0;
-                            if let Some(ref _hk) = hotkey_handler {
-                                if _hk.is_active && !(ctrl_down && shift_down && f_down) {
+                            if let Some(ref mut hk) = hotkey_handler {
+                                if hk.reveal_active && !(ctrl_down && shift_down && f_down) {
                                     log::info!("Hotkey reveal released");
+                                    hk.reveal_active = false;
+                                    if let Some(ref mut rv) = overlays.reveal {
+                                        rv.release();
+                                    }
                                 }
                             }
                         }
+                        TIMER_ANIMATION => {
+                            // Tick every overlay — no short-circuit, both may be animating
+                            let border_animating = match overlays.border {
+                                Some(ref mut bo) => bo.tick(),
+                                None => false,
+                            };
+                            let flash_animating = match overlays.flash {
+                                Some(ref mut fo) => fo.tick(),
+                                None => false,
+                            };
+                            let reveal_animating = match overlays.reveal {
+                                Some(ref mut rv) => rv.tick(),
+                                None => false,
+                            };
+                            let locator_animating = match overlays.locator {
+                                Some(ref mut lc) => lc.tick(),
+                                None => false,
+                            };
+                            let animating =
+                                border_animating || flash_animating || reveal_animating || locator_animating;
+                            if !animating {
+                                KillTimer(Some(msg_hwnd), TIMER_ANIMATION).ok();
+                                animation_timer_active = false;
+                            }
+                        }
                         TIMER_SETTINGS_POLL => {
-                            poll_settings(
-                                &mut settings_child,
-                                &mut app,
-                                &mut border_overlay,
-                                &flash_overlay,
-                                &mut indicators,
-                                &tray,
-                                msg_hwnd,
-                            );
+                            poll_settings(&mut settings_child, &mut app, &mut overlays, &tray, msg_hwnd);
+                        }
+                        TIMER_CURSOR_POLL => {
+                            watch_cursor(&app, &mut overlays, &mut cursor_watch);
+                        }
+                        TIMER_PICKER_POLL => {
+                            poll_picker(&mut picker_child, &app, &mut overlays, msg_hwnd);
+                        }
+                        TIMER_SYSTEM_COLORS_POLL => {
+                            let mut new_config = app.config.clone();
+                            theme::resolve_system_colors_from(&app.theme_colors, &mut new_config);
+                            if new_config != app.config {
+                                log::info!("System colors changed");
+                                apply_config(new_config, &mut app, &mut overlays, &tray, msg_hwnd);
+                            }
                         }
                         _ => {}
                     }
@@ -235,16 +498,15 @@ fn main() {
                             t.update_border_text(app.config.border_enabled);
                         }
                         if !app.config.border_enabled {
-                            if let Some(ref bo) = border_overlay {
+                            if let Some(ref bo) = overlays.border {
                                 bo.hide();
                             }
+                            // The tab drops down onto the window edge
+                            if let Some(rect) = app.focus.as_ref().map(|f| f.window_rect) {
+                                update_title_tab(&app, &mut overlays, &rect);
+                            }
                         } else {
-                            update_focus_state(
-                                &mut app,
-                                &mut border_overlay,
-                                &flash_overlay,
-                                &mut indicators,
-                            );
+                            update_focus_state(&mut app, &mut overlays);
                         }
                     }
                     MENU_TOGGLE_FLASH => {
@@ -266,12 +528,23 @@ fn main() {
                         if let Some(ref t) = tray {
                             t.update_indicator_text(app.config.indicator_enabled);
                         }
-                        if let Some(ref ind) = indicators {
-                            if app.config.indicator_enabled {
-                                ind.show_all();
-                            } else {
-                                ind.hide_all();
+                        overlays.apply_indicator_visibility(&app.config);
+                    }
+                    MENU_TOGGLE_SPOTLIGHT => {
+                        app.config.spotlight_enabled = !app.config.spotlight_enabled;
+                        log::info!(
+                            "Spotlight: {}",
+                            if app.config.spotlight_enabled { "ON" } else { "OFF" }
+                        );
+                        if let Some(ref t) = tray {
+                            t.update_spotlight_text(app.config.spotlight_enabled);
+                        }
+                        if !app.config.spotlight_enabled {
+                            if let Some(ref sp) = overlays.spotlight {
+                                sp.hide();
                             }
+                        } else {
+                            update_focus_state(&mut app, &mut overlays);
                         }
                     }
                     MENU_BORDER_STYLE => {
@@ -281,7 +554,7 @@ fn main() {
                         if let Some(ref t) = tray {
                             t.update_border_style_text(new_style.label());
                         }
-                        if let Some(ref mut bo) = border_overlay {
+                        if let Some(ref mut bo) = overlays.border {
                             bo.set_style(new_style);
                             // Re-apply to current focus
                             if app.config.border_enabled {
@@ -294,6 +567,10 @@ fn main() {
                                 }
                             }
                         }
+                        // Glow is wider than the other styles; re-seat the tab
+                        if let Some(rect) = app.focus.as_ref().map(|f| f.window_rect) {
+                            update_title_tab(&app, &mut overlays, &rect);
+                        }
                     }
                     MENU_SETTINGS => {
                         if settings_child.is_none() {
@@ -312,10 +589,41 @@ fn main() {
                         log::info!("Quit requested");
                         PostQuitMessage(0);
                     }
+                    other if other.starts_with(MENU_THEME_PREFIX) => {
+                        let name = &other[MENU_THEME_PREFIX.len()..];
+                        match theme::find(name) {
+                            Some(theme) => {
+                                log::info!("Theme: {}", theme.name);
+                                let mut new_config = app.config.clone();
+                                theme.apply(&mut new_config);
+                                app.theme_colors = ThemeColors::from_config(&new_config);
+                                theme::resolve_system_colors(&mut new_config);
+                                apply_config(new_config, &mut app, &mut overlays, &tray, msg_hwnd);
+                            }
+                            None => {
+                                log::warn!("Theme '{}' is no longer available", name);
+                                // The item toggled itself on click; restore the checks
+                                if let Some(ref t) = tray {
+                                    t.update_theme(&app.config.theme);
+                                }
+                            }
+                        }
+                    }
                     _ => {}
                 }
             }
 
+            // Start the frame timer if anything above kicked off an animation
+            if !animation_timer_active
+                && (overlays.border.as_ref().is_some_and(|bo| bo.is_animating())
+                    || overlays.flash.as_ref().is_some_and(|fo| fo.is_animating())
+                    || overlays.reveal.as_ref().is_some_and(|rv| rv.is_animating())
+                    || overlays.locator.as_ref().is_some_and(|lc| lc.is_animating()))
+            {
+                SetTimer(Some(msg_hwnd), TIMER_ANIMATION, ANIMATION_FRAME_MS, None);
+                animation_timer_active = true;
+            }
+
             let _ = TranslateMessage(&msg);
             DispatchMessageW(&msg);
         }
@@ -325,26 +633,133 @@ fn main() {
     if let Some(ref mut child) = settings_child {
         let _ = child.kill();
     }
+    if let Some(ref mut child) = picker_child {
+        let _ = child.kill();
+    }
 
     // Cleanup
     tracker::unhook(focus_hook, location_hook);
+    warp::watch_keyboard(false);
     unsafe {
         KillTimer(Some(msg_hwnd), TIMER_POLL).ok();
         KillTimer(Some(msg_hwnd), TIMER_HOTKEY_CHECK).ok();
         KillTimer(Some(msg_hwnd), TIMER_SETTINGS_POLL).ok();
+        KillTimer(Some(msg_hwnd), TIMER_ANIMATION).ok();
+        KillTimer(Some(msg_hwnd), TIMER_PICKER_POLL).ok();
+        KillTimer(Some(msg_hwnd), TIMER_CURSOR_POLL).ok();
+        KillTimer(Some(msg_hwnd), TIMER_SYSTEM_COLORS_POLL).ok();
         let _ = DestroyWindow(msg_hwnd);
     }
 
     log::info!("whereismywindow exiting");
 }
 
+/// Cursor state sampled by TIMER_CURSOR_POLL for the locator triggers.
+#[derive(Default)]
+struct CursorWatch {
+    shake: ShakeDetector,
+    /// Monitor the cursor was on at the last sample.
+    monitor: Option<usize>,
+}
+
+/// Sample the cursor only while a trigger needs it.
+fn update_cursor_timer(config: &Config, msg_hwnd: HWND) {
+    unsafe {
+        if config.locator_on_shake || config.locator_on_monitor_change {
+            SetTimer(Some(msg_hwnd), TIMER_CURSOR_POLL, CURSOR_POLL_MS, None);
+        } else {
+            KillTimer(Some(msg_hwnd), TIMER_CURSOR_POLL).ok();
+        }
+    }
+}
+
+/// Re-read the system colors only while the colors follow them.
+fn update_system_colors_timer(config: &Config, msg_hwnd: HWND) {
+    unsafe {
+        if config.accent_enabled || config.high_contrast == HighContrast::FollowSystem {
+            SetTimer(Some(msg_hwnd), TIMER_SYSTEM_COLORS_POLL, SYSTEM_COLORS_POLL_MS, None);
+        } else {
+            KillTimer(Some(msg_hwnd), TIMER_SYSTEM_COLORS_POLL).ok();
+        }
+    }
+}
+
+/// Cursor position and the index of the monitor it is on.
+fn cursor_position(app: &App) -> Option<(POINT, usize)> {
+    let mut point = POINT::default();
+    unsafe { GetCursorPos(&mut point).ok()? };
+    let monitor_rects: Vec<_> = app.monitors.iter().map(|m| m.full_rect).collect();
+    let index = geometry::monitor_at_point(point.x, point.y, &monitor_rects)?;
+    Some((point, index))
+}
+
+/// Move the cursor into the focused window, at its center or where the
+/// cursor last was, depending on the warp target.
+fn warp_cursor_to(app: &App, focus: &FocusState) {
+    let remembered = app.cursor_memory.recall(focus.hwnd);
+    let (x, y) = warp::warp_point(&focus.window_rect, app.config.warp_target, remembered);
+    warp::move_cursor(x, y);
+}
+
+/// Ripple around the cursor on its monitor.
+fn locate_cursor(app: &App, overlays: &mut Overlays) {
+    let (Some(lc), Some((point, index))) = (overlays.locator.as_mut(), cursor_position(app)) else {
+        return;
+    };
+    lc.locate(point.x, point.y, &app.monitors[index].full_rect);
+}
+
+/// Fire the locator when the mouse is shaken or crosses onto another monitor.
+fn watch_cursor(app: &App, overlays: &mut Overlays, watch: &mut CursorWatch) {
+    let Some((point, index)) = cursor_position(app) else { return };
+    let shaken = watch.shake.push(Instant::now(), point.x, point.y) && app.config.locator_on_shake;
+    let crossed = watch.monitor.is_some_and(|prev| prev != index) && app.config.locator_on_monitor_change;
+    watch.monitor = Some(index);
+    if shaken || crossed {
+        if let Some(ref mut lc) = overlays.locator {
+            lc.locate(point.x, point.y, &app.monitors[index].full_rect);
+        }
+    }
+}
+
+/// Poll the picker subprocess and flash the chosen window's monitor when it exits.
+fn poll_picker(
+    picker_child: &mut Option<std::process::Child>,
+    app: &App,
+    overlays: &mut Overlays,
+    msg_hwnd: HWND,
+) {
+    let Some(child) = picker_child.as_mut() else {
+        return;
+    };
+    let chosen = match picker::launch::poll_child(child) {
+        Some(chosen) => chosen,
+        None => return, // still running
+    };
+
+    *picker_child = None;
+    unsafe {
+        KillTimer(Some(msg_hwnd), TIMER_PICKER_POLL).ok();
+    }
+
+    // The picker already activated the window; focus tracking picks that up
+    let Some(hwnd) = chosen.filter(|_| app.config.flash_enabled) else { return };
+    let Some(window_rect) = window_info::get_extended_frame_bounds(HWND(hwnd as *mut _)) else {
+        return;
+    };
+    let monitor_rects: Vec<_> = app.monitors.iter().map(|m| m.full_rect).collect();
+    let index = geometry::best_monitor_index(&window_rect, &monitor_rects);
+    if let (Some(fo), Some(monitor_rect)) = (overlays.flash.as_mut(), monitor_rects.get(index)) {
+        let clamped = clamp_to_monitor(&window_rect, monitor_rect);
+        fo.flash(monitor_rect, &clamped, app.config.flash_target, 1.0);
+    }
+}
+
 /// Poll the settings subprocess and apply changes when it exits.
 fn poll_settings(
     settings_child: &mut Option<std::process::Child>,
     app: &mut App,
-    border_overlay: &mut Option<BorderOverlay>,
-    flash_overlay: &Option<FlashOverlay>,
-    indicators: &mut Option<MonitorIndicators>,
+    overlays: &mut Overlays,
     tray: &Option<SystemTray>,
     msg_hwnd: HWND,
 ) {
@@ -370,11 +785,22 @@ fn poll_settings(
     }
 
     // Reload config from disk (subprocess already saved it)
-    let new_config = settings::persistence::load_config();
+    let (new_config, theme_colors) = settings::persistence::load_config_with_theme_colors();
+    app.theme_colors = theme_colors;
     log::info!("Applying reloaded settings");
+    apply_config(new_config, app, overlays, tray, msg_hwnd);
+}
 
+/// Switch to `new_config`, updating the overlays and tray for what changed.
+fn apply_config(
+    new_config: Config,
+    app: &mut App,
+    overlays: &mut Overlays,
+    tray: &Option<SystemTray>,
+    msg_hwnd: HWND,
+) {
     // Update border overlay
-    if let Some(ref mut bo) = border_overlay {
+    if let Some(ref mut bo) = overlays.border {
         if app.config.border_color != new_config.border_color {
             bo.set_color(new_config.border_color);
         }
@@ -384,30 +810,102 @@ fn poll_settings(
         if app.config.border_style != new_config.border_style {
             bo.set_style(new_config.border_style);
         }
+        if app.config.gradient_stops != new_config.gradient_stops
+            || app.config.gradient_rotate != new_config.gradient_rotate
+            || app.config.gradient_rotation_ms != new_config.gradient_rotation_ms
+        {
+            bo.set_gradient(
+                &new_config.gradient_stops,
+                new_config.gradient_rotate,
+                new_config.gradient_rotation_ms,
+            );
+        }
+        bo.set_dash_pattern(new_config.dash_length, new_config.dash_gap);
+        bo.set_glow(new_config.glow_width, new_config.glow_layers, new_config.glow_falloff);
+        bo.set_render_mode(new_config.render_mode);
+        bo.set_animation(
+            new_config.animate_transitions,
+            new_config.animation_duration_ms,
+            new_config.animation_easing,
+        );
+    }
+
+    // Update flash appearance
+    if let Some(ref mut fo) = overlays.flash {
+        fo.set_opacity(new_config.flash_opacity);
+        fo.set_color(&new_config.effective_flash_color());
+        fo.set_shape(new_config.flash_shape);
+        fo.set_duration(new_config.flash_duration_ms, new_config.flash_fade_ms);
+        if app.config.render_mode != new_config.render_mode {
+            fo.set_render_mode(new_config.render_mode);
+        }
     }
 
-    // Update flash opacity
-    if let Some(ref fo) = flash_overlay {
-        if (app.config.flash_opacity - new_config.flash_opacity).abs() > f32::EPSILON {
-            fo.set_opacity(new_config.flash_opacity);
+    // Update spotlight opacity
+    if let Some(ref sp) = overlays.spotlight {
+        if (app.config.spotlight_opacity - new_config.spotlight_opacity).abs() > f32::EPSILON {
+            sp.set_opacity(new_config.spotlight_opacity);
         }
     }
 
     // Update indicator visibility
-    if app.config.indicator_enabled != new_config.indicator_enabled {
-        if let Some(ref ind) = indicators {
-            if new_config.indicator_enabled {
-                ind.show_all();
-            } else {
-                ind.hide_all();
-            }
+    if app.config.indicator_enabled != new_config.indicator_enabled
+        || app.config.indicator_mode != new_config.indicator_mode
+    {
+        overlays.apply_indicator_visibility(&new_config);
+    }
+
+    // Update the badge colors (the active one follows the border by default)
+    if app.config.effective_badge_color() != new_config.effective_badge_color() {
+        if let Some(ref mut ind) = overlays.indicators {
+            ind.set_active_color(&new_config.effective_badge_color());
+        }
+        if let Some(ref mut mm) = overlays.minimap {
+            mm.set_active_color(&new_config.effective_badge_color());
+        }
+    }
+    if app.config.badge_inactive_color != new_config.badge_inactive_color {
+        if let Some(ref mut ind) = overlays.indicators {
+            ind.set_inactive_color(&new_config.badge_inactive_color);
         }
     }
 
-    // Update indicator badge active color to match border color
     if app.config.border_color != new_config.border_color {
-        if let Some(ref mut ind) = indicators {
-            ind.set_active_color(&new_config.border_color);
+        if let Some(ref mut tab) = overlays.title_tab {
+            tab.set_color(&new_config.border_color);
+        }
+        if let Some(ref mut rv) = overlays.reveal {
+            rv.set_accent(&new_config.border_color);
+        }
+        if let Some(ref mut lc) = overlays.locator {
+            lc.set_color(&new_config.border_color);
+        }
+    }
+
+    let styles = badge_styles(&new_config, &app.monitors);
+    if let Some(ref mut ind) = overlays.indicators {
+        ind.set_styles(&styles);
+    }
+    if let Some(ref mut mm) = overlays.minimap {
+        mm.set_layout(&styles, new_config.minimap_width);
+        mm.set_scope(new_config.minimap_scope);
+    }
+
+    if app.config.render_mode != new_config.render_mode {
+        if let Some(ref mut ind) = overlays.indicators {
+            ind.set_render_mode(new_config.render_mode);
+        }
+        if let Some(ref mut mm) = overlays.minimap {
+            mm.set_render_mode(new_config.render_mode);
+        }
+        if let Some(ref mut tab) = overlays.title_tab {
+            tab.set_render_mode(new_config.render_mode);
+        }
+        if let Some(ref mut rv) = overlays.reveal {
+            rv.set_render_mode(new_config.render_mode);
+        }
+        if let Some(ref mut lc) = overlays.locator {
+            lc.set_render_mode(new_config.render_mode);
         }
     }
 
@@ -419,59 +917,92 @@ fn poll_settings(
         t.update_border_text(new_config.border_enabled);
         t.update_flash_text(new_config.flash_enabled);
         t.update_indicator_text(new_config.indicator_enabled);
+        t.update_spotlight_text(new_config.spotlight_enabled);
         t.update_border_style_text(new_config.border_style.label());
+        t.update_theme(&new_config.theme);
     }
 
     // Apply config (auto-start already handled by subprocess)
     app.config = new_config;
+    update_cursor_timer(&app.config, msg_hwnd);
+    update_system_colors_timer(&app.config, msg_hwnd);
+    warp::watch_keyboard(app.config.warp_on_monitor_change);
+
+    // Per-monitor colors win over the global ones just applied
+    if let Some(index) = app.focus.as_ref().map(|f| f.monitor_index) {
+        apply_monitor_colors(app, overlays, index);
+    }
+
+    // Badge templates and labels may have changed
+    if let Some(ref mut ind) = overlays.indicators {
+        ind.set_texts(&badge_texts(app));
+    }
 
     // If border was just enabled or settings changed, re-apply to current focus
     if app.config.border_enabled {
         if let Some(ref focus) = app.focus {
             let clamped = clamp_to_monitor(&focus.window_rect, &focus.monitor_rect);
-            if let Some(ref mut bo) = border_overlay {
+            let radius = border_corner_radius(
+                app,
+                focus.hwnd,
+                &focus.window_rect,
+                focus.monitor_index,
+            );
+            if let Some(ref mut bo) = overlays.border {
+                bo.set_corner_radius(radius);
                 bo.move_to(&clamped);
             }
         }
     } else if border_was_enabled {
-        if let Some(ref bo) = border_overlay {
+        if let Some(ref bo) = overlays.border {
             bo.hide();
         }
     }
+
+    if let Some(ref focus) = app.focus {
+        let radius = border_corner_radius(app, focus.hwnd, &focus.window_rect, focus.monitor_index);
+        update_spotlight(
+            app,
+            &mut overlays.spotlight,
+            focus.hwnd,
+            &focus.window_rect,
+            &focus.monitor_rect,
+            radius,
+        );
+        let window_rect = focus.window_rect;
+        update_title_tab(app, overlays, &window_rect);
+    }
 }
 
 /// Query current foreground window and update app state + overlays.
-fn update_focus_state(
-    app: &mut App,
-    border_overlay: &mut Option<BorderOverlay>,
-    flash_overlay: &Option<FlashOverlay>,
-    indicators: &mut Option<MonitorIndicators>,
-) {
+fn update_focus_state(app: &mut App, overlays: &mut Overlays) {
+    // Window counts may have changed even when focus lands on nothing
+    if let Some(ref mut ind) = overlays.indicators {
+        ind.set_texts(&badge_texts(app));
+    }
+
     let Some(snapshot) = window_info::get_foreground_window_info() else {
         // Focus went to desktop, taskbar, minimized window, etc.
         // Hide the border so it doesn't linger on a stale position.
-        if let Some(ref bo) = border_overlay {
+        if let Some(ref bo) = overlays.border {
             bo.hide();
         }
+        if let Some(ref sp) = overlays.spotlight {
+            sp.hide();
+        }
+        if let Some(ref tab) = overlays.title_tab {
+            tab.hide();
+        }
+        if let Some(ref mut mm) = overlays.minimap {
+            mm.set_focus(app.prev_monitor_index.unwrap_or(0), None);
+        }
         app.focus = None;
         return;
     };
 
     // Skip our own overlay windows
-    if let Some(ref bo) = border_overlay {
-        if snapshot.hwnd == bo.hwnd.0 as isize {
-            return;
-        }
-    }
-    if let Some(ref fo) = flash_overlay {
-        if snapshot.hwnd == fo.hwnd.0 as isize {
-            return;
-        }
-    }
-    if let Some(ref ind) = indicators {
-        if ind.hwnd_list().contains(&snapshot.hwnd) {
-            return;
-        }
+    if overlays.owns(snapshot.hwnd) {
+        return;
     }
 
     // Find which monitor
@@ -512,40 +1043,175 @@ fn update_focus_state(
         monitor_name.trim_end_matches('\0'),
     );
 
+    let window_changed = app.focus.as_ref().is_some_and(|f| f.hwnd != snapshot.hwnd);
+    let cursor = cursor_position(app);
+    if window_changed {
+        if let (Some(prev), Some((point, _))) = (app.focus.as_ref(), cursor) {
+            app.cursor_memory.remember(prev.hwnd, &prev.window_rect, point.x, point.y);
+        }
+    }
     let monitor_changed = app.update_focus(focus_state);
 
+    // A keyboard switch leaves the cursor behind on the old monitor; a click
+    // on the new window would already have brought it along. Focus taken by
+    // another program without the user's input doesn't pull the cursor.
+    let cursor_left_behind = cursor.is_some_and(|(_, index)| index != monitor_index);
+    if monitor_changed
+        && cursor_left_behind
+        && warp::switched_by_keyboard()
+        && app.config.warp_on_monitor_change
+        && !warp::is_excluded(&snapshot.exe_name, &app.config.warp_exclude)
+    {
+        if let Some(ref focus) = app.focus {
+            warp_cursor_to(app, focus);
+        }
+    }
+
+    apply_monitor_colors(app, overlays, monitor_index);
+
+    let radius = border_corner_radius(app, snapshot.hwnd, &snapshot.rect, monitor_index);
+
+    // Spotlight before the border, so the border is raised above it
+    update_spotlight(
+        app,
+        &mut overlays.spotlight,
+        snapshot.hwnd,
+        &snapshot.rect,
+        &monitor_rect,
+        radius,
+    );
+
     // Update border overlay — use move_to on focus change to hide→move→show
     if app.config.border_enabled {
         let clamped = clamp_to_monitor(&snapshot.rect, &monitor_rect);
-        if let Some(ref mut bo) = border_overlay {
+        if let Some(ref mut bo) = overlays.border {
+            bo.set_corner_radius(radius);
             bo.move_to(&clamped);
         }
     }
 
+    // Title tab after the border, so it is raised above it
+    update_title_tab(app, overlays, &snapshot.rect);
+    if let Some(ref tab) = overlays.title_tab {
+        tab.bring_to_front();
+    }
+
     // Update monitor indicators
     if app.config.indicator_enabled {
-        if let Some(ref mut ind) = indicators {
+        if let Some(ref mut ind) = overlays.indicators {
             ind.set_active(monitor_index);
         }
+        if let Some(ref mut mm) = overlays.minimap {
+            mm.set_focus(monitor_index, Some(&snapshot.rect));
+        }
     }
 
-    // Flash on monitor change
-    if monitor_changed && app.config.flash_enabled {
-        if let Some(ref fo) = flash_overlay {
-            fo.flash(&monitor_rect);
-            unsafe {
-                let msg_hwnd = HWND(tracker::msg_hwnd_value() as *mut _);
-                SetTimer(
-                    Some(msg_hwnd),
-                    TIMER_FLASH_HIDE,
-                    app.config.flash_duration_ms,
-                    None,
-                );
+    // Flash on monitor change, or a subtle window flash within a monitor
+    if app.config.flash_enabled {
+        if let Some(ref mut fo) = overlays.flash {
+            let clamped = clamp_to_monitor(&snapshot.rect, &monitor_rect);
+            if monitor_changed {
+                fo.flash(&monitor_rect, &clamped, app.config.flash_target, 1.0);
+            } else if window_changed && app.config.flash_same_monitor {
+                fo.flash(&monitor_rect, &clamped, FlashTarget::Window, SUBTLE_FLASH_STRENGTH);
             }
         }
     }
 }
 
+/// Switch the border, title tab, flash and active badge colors to those of
+/// the monitor `monitor_index`, which may override the global ones. The
+/// high-contrast palette wins over the monitor's own colors.
+fn apply_monitor_colors(app: &App, overlays: &mut Overlays, monitor_index: usize) {
+    let Some(monitor) = app.monitors.get(monitor_index) else { return };
+    let config = &app.config;
+    let (border, flash, badge) = if contrast::is_active(config.high_contrast) {
+        (config.border_color, config.effective_flash_color(), config.effective_badge_color())
+    } else {
+        let id = enumeration::monitor_id(&monitor.name);
+        (config.monitor_border_color(id), config.monitor_flash_color(id), config.monitor_badge_color(id))
+    };
+
+    if let Some(ref mut bo) = overlays.border {
+        bo.set_color(border);
+    }
+    if let Some(ref mut tab) = overlays.title_tab {
+        tab.set_color(&border);
+    }
+    if let Some(ref mut fo) = overlays.flash {
+        fo.set_color(&flash);
+    }
+
+    if let Some(ref mut ind) = overlays.indicators {
+        ind.set_active_color(&badge);
+    }
+    if let Some(ref mut mm) = overlays.minimap {
+        mm.set_active_color(&badge);
+    }
+}
+
+/// Whether any enabled overlay needs to follow the focused window as it moves.
+fn follows_window_moves(config: &Config) -> bool {
+    config.border_enabled
+        || config.spotlight_enabled
+        || config.title_tab_enabled
+        || (config.indicator_enabled && config.indicator_mode == IndicatorMode::MiniMap)
+}
+
+/// Cut the spotlight out around the focused window. Hidden while disabled
+/// and while the window is fullscreen, where dimming would only get in the way.
+fn update_spotlight(
+    app: &App,
+    spotlight: &mut Option<SpotlightOverlay>,
+    hwnd: isize,
+    window_rect: &RECT,
+    monitor_rect: &RECT,
+    corner_radius: f32,
+) {
+    let Some(sp) = spotlight.as_mut() else { return };
+    let hwnd = HWND(hwnd as *mut _);
+    let fullscreen = geometry::is_fullscreen(
+        window_rect,
+        monitor_rect,
+        window_info::is_maximized(hwnd),
+        window_info::has_caption(hwnd),
+    );
+    if !app.config.spotlight_enabled || fullscreen {
+        sp.hide();
+        return;
+    }
+    sp.update(&clamp_to_monitor(window_rect, monitor_rect), corner_radius);
+}
+
+/// Attach the title tab to the focused window's border, or hide it while disabled.
+fn update_title_tab(app: &App, overlays: &mut Overlays, window_rect: &RECT) {
+    let Some(tab) = overlays.title_tab.as_mut() else { return };
+    let Some(focus) = app.focus.as_ref().filter(|_| app.config.title_tab_enabled) else {
+        tab.hide();
+        return;
+    };
+
+    let border = match overlays.border {
+        Some(ref bo) if app.config.border_enabled => bo.effective_thickness().ceil() as i32,
+        _ => 0,
+    };
+    let clamped = clamp_to_monitor(window_rect, &focus.monitor_rect);
+    tab.show(&title_tab_text(app, focus), &clamped, &focus.monitor_rect, border);
+}
+
+/// "code.exe · Monitor 2 · main.rs - Visual Studio Code", per the title tab options.
+/// The title comes last since it is the first thing cut when space runs out.
+fn title_tab_text(app: &App, focus: &FocusState) -> String {
+    let mut parts = vec![focus.exe_name.clone()];
+    if app.config.title_tab_show_monitor {
+        parts.push(format!("Monitor {}", focus.monitor_index + 1));
+    }
+    if app.config.title_tab_show_title && !focus.title.is_empty() {
+        parts.push(focus.title.clone());
+    }
+    parts.join(" · ")
+}
+
 fn show_reveal_info(app: &App) {
     for (i, m) in app.monitors.iter().enumerate() {
         let focused = app
@@ -570,6 +1236,112 @@ fn show_reveal_info(app: &App) {
     }
 }
 
+/// Identify-card contents for each monitor, in monitor order, with each
+/// monitor's top-level windows listed topmost first.
+fn reveal_cards(app: &App) -> Vec<CardInfo> {
+    let monitor_rects: Vec<_> = app.monitors.iter().map(|m| m.full_rect).collect();
+    let focused_hwnd = app.focus.as_ref().map(|f| f.hwnd);
+    let mut windows: Vec<Vec<CardWindow>> = app.monitors.iter().map(|_| Vec::new()).collect();
+    for w in window_info::enumerate_app_windows() {
+        let index = geometry::best_monitor_index(&w.rect, &monitor_rects);
+        if let Some(list) = windows.get_mut(index) {
+            list.push(CardWindow {
+                focused: Some(w.hwnd) == focused_hwnd,
+                exe_name: w.exe_name,
+                title: w.title,
+            });
+        }
+    }
+
+    app.monitors
+        .iter()
+        .zip(windows)
+        .enumerate()
+        .map(|(i, (m, windows))| {
+            let id = enumeration::monitor_id(&m.name);
+            CardInfo {
+                number: i + 1,
+                label: app
+                    .config
+                    .monitor_label(id)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("Monitor {}", i + 1)),
+                width: m.full_rect.right - m.full_rect.left,
+                height: m.full_rect.bottom - m.full_rect.top,
+                has_focus: app.focus.as_ref().is_some_and(|f| f.monitor_index == i),
+                windows,
+            }
+        })
+        .collect()
+}
+
+/// Corner radius (in physical px) the border should use for a window.
+/// Maximized and snapped windows are drawn square by Windows 11, so the
+/// border follows suit regardless of the configured corner style.
+fn border_corner_radius(app: &App, hwnd: isize, window_rect: &RECT, monitor_index: usize) -> f32 {
+    if app.config.corner_style == CornerStyle::Square {
+        return 0.0;
+    }
+
+    let hwnd = HWND(hwnd as *mut _);
+    if window_info::is_maximized(hwnd) {
+        return 0.0;
+    }
+    if let Some(m) = app.monitors.get(monitor_index) {
+        if geometry::is_snapped(window_rect, &m.work_rect) {
+            return 0.0;
+        }
+    }
+
+    let radius = match app.config.corner_style {
+        CornerStyle::Manual => app.config.corner_radius,
+        _ => window_info::get_system_corner_radius(hwnd),
+    };
+    radius * window_info::get_dpi_scale(hwnd)
+}
+
+/// Each monitor's badge style, in `monitors` order.
+fn badge_styles(config: &Config, monitors: &[MonitorInfo]) -> Vec<BadgeStyle> {
+    monitors
+        .iter()
+        .map(|m| config.badge_style(enumeration::monitor_id(&m.name)))
+        .collect()
+}
+
+/// Each monitor's badge text expanded from its template, in `app.monitors` order.
+fn badge_texts(app: &App) -> Vec<String> {
+    let monitor_rects: Vec<_> = app.monitors.iter().map(|m| m.full_rect).collect();
+    let ids: Vec<_> = app.monitors.iter().map(|m| enumeration::monitor_id(&m.name)).collect();
+
+    // Enumerating windows is only worth it when a template shows the count
+    let mut window_counts = vec![0; app.monitors.len()];
+    if ids.iter().any(|id| app.config.badge_template(id).contains("{windows}")) {
+        for w in window_info::enumerate_app_windows() {
+            let index = geometry::best_monitor_index(&w.rect, &monitor_rects);
+            if let Some(count) = window_counts.get_mut(index) {
+                *count += 1;
+            }
+        }
+    }
+
+    app.monitors
+        .iter()
+        .zip(&ids)
+        .enumerate()
+        .map(|(i, (m, id))| {
+            let number = (i + 1).to_string();
+            let fields = BadgeFields {
+                number: i + 1,
+                label: app.config.monitor_label(id).unwrap_or(&number),
+                width: m.full_rect.right - m.full_rect.left,
+                height: m.full_rect.bottom - m.full_rect.top,
+                windows: window_counts[i],
+            };
+            indicator::format_badge(app.config.badge_template(id), &fields)
+        })
+        .collect()
+}
+
 /// Clamp a window rect so it doesn't extend beyond its monitor.
 /// Prevents the border overlay from leaking onto adjacent monitors
 /// (maximized windows have a few px overscan beyond the screen edge).
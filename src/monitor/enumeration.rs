@@ -73,3 +73,10 @@ pub fn format_monitor(info: &MonitorInfo, index: usize) -> String {
         r.top,
     )
 }
+
+/// Short id used to key per-monitor config: the device name without its
+/// `\\.\` prefix, e.g. `DISPLAY2`.
+pub fn monitor_id(name: &str) -> &str {
+    let name = name.trim_end_matches('\0');
+    name.strip_prefix(r"\\.\").unwrap_or(name)
+}
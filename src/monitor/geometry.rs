@@ -16,3 +16,136 @@ pub fn best_monitor_index(window_rect: &RECT, monitor_rects: &[RECT]) -> usize {
         .map(|(i, _)| i)
         .unwrap_or(0)
 }
+
+/// Whether a window rect matches a snap layout of the work area: a half
+/// (full height, half width) or a quarter (half width, half height) anchored
+/// to its edges. Windows 11 draws snapped windows with square corners; a
+/// window merely dragged against an edge or into a corner keeps them round.
+pub fn is_snapped(window_rect: &RECT, work_rect: &RECT) -> bool {
+    let half_width = is_half(window_rect.right - window_rect.left, work_rect.right - work_rect.left)
+        && (window_rect.left == work_rect.left || window_rect.right == work_rect.right);
+    let full_height = window_rect.top == work_rect.top && window_rect.bottom == work_rect.bottom;
+    let half_height = is_half(window_rect.bottom - window_rect.top, work_rect.bottom - work_rect.top)
+        && (window_rect.top == work_rect.top || window_rect.bottom == work_rect.bottom);
+    half_width && (full_height || half_height)
+}
+
+/// Whether `len` is half of `total`, either way of an odd split.
+fn is_half(len: i32, total: i32) -> bool {
+    (len * 2 - total).abs() <= 1
+}
+
+/// Whether a window covers its entire monitor, taskbar included (games,
+/// videos, presentations). With an auto-hiding taskbar an ordinary maximized
+/// window covers the monitor too, so a maximized window only counts when it
+/// has no title bar.
+pub fn is_fullscreen(window_rect: &RECT, monitor_rect: &RECT, maximized: bool, has_caption: bool) -> bool {
+    let covers = window_rect.left <= monitor_rect.left
+        && window_rect.top <= monitor_rect.top
+        && window_rect.right >= monitor_rect.right
+        && window_rect.bottom >= monitor_rect.bottom;
+    covers && !(maximized && has_caption)
+}
+
+/// Convert a rect in workspace coordinates (`WINDOWPLACEMENT`), which are
+/// relative to the monitor's work area, to screen coordinates. They differ
+/// when the taskbar is docked at the top or left.
+pub fn workspace_to_screen(rect: &RECT, monitor_rect: &RECT, work_rect: &RECT) -> RECT {
+    let dx = work_rect.left - monitor_rect.left;
+    let dy = work_rect.top - monitor_rect.top;
+    RECT {
+        left: rect.left + dx,
+        top: rect.top + dy,
+        right: rect.right + dx,
+        bottom: rect.bottom + dy,
+    }
+}
+
+/// Index of the monitor containing a point (right/bottom edges exclusive).
+pub fn monitor_at_point(x: i32, y: i32, monitor_rects: &[RECT]) -> Option<usize> {
+    monitor_rects
+        .iter()
+        .position(|r| x >= r.left && x < r.right && y >= r.top && y < r.bottom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORK: RECT = RECT { left: 0, top: 0, right: 1920, bottom: 1040 };
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> RECT {
+        RECT { left, top, right, bottom }
+    }
+
+    #[test]
+    fn half_snaps_are_snapped() {
+        assert!(is_snapped(&rect(0, 0, 960, 1040), &WORK));
+        assert!(is_snapped(&rect(960, 0, 1920, 1040), &WORK));
+    }
+
+    #[test]
+    fn quarter_snaps_are_snapped() {
+        assert!(is_snapped(&rect(0, 0, 960, 520), &WORK));
+        assert!(is_snapped(&rect(960, 520, 1920, 1040), &WORK));
+    }
+
+    #[test]
+    fn odd_work_area_splits_either_way() {
+        let work = rect(0, 0, 1921, 1041);
+        assert!(is_snapped(&rect(0, 0, 960, 1041), &work));
+        assert!(is_snapped(&rect(960, 0, 1921, 1041), &work));
+        assert!(is_snapped(&rect(0, 520, 961, 1041), &work));
+    }
+
+    #[test]
+    fn window_dragged_into_a_corner_is_not_snapped() {
+        assert!(!is_snapped(&rect(0, 0, 800, 600), &WORK));
+        assert!(!is_snapped(&rect(1200, 500, 1920, 1040), &WORK));
+    }
+
+    #[test]
+    fn full_height_window_of_other_width_is_not_snapped() {
+        assert!(!is_snapped(&rect(0, 0, 1200, 1040), &WORK));
+        assert!(!is_snapped(&rect(400, 0, 1360, 1040), &WORK));
+    }
+
+    #[test]
+    fn borderless_window_covering_the_monitor_is_fullscreen() {
+        let monitor = rect(0, 0, 1920, 1080);
+        assert!(is_fullscreen(&monitor, &monitor, false, false));
+        assert!(is_fullscreen(&monitor, &monitor, true, false));
+        assert!(is_fullscreen(&rect(-1, -1, 1921, 1081), &monitor, false, false));
+    }
+
+    #[test]
+    fn maximized_window_over_auto_hide_taskbar_is_not_fullscreen() {
+        let monitor = rect(0, 0, 1920, 1080);
+        // Maximized frames overhang the monitor by the resize border
+        assert!(!is_fullscreen(&rect(-8, -8, 1928, 1088), &monitor, true, true));
+    }
+
+    #[test]
+    fn window_short_of_the_monitor_is_not_fullscreen() {
+        let monitor = rect(0, 0, 1920, 1080);
+        assert!(!is_fullscreen(&WORK, &monitor, true, true));
+        assert!(!is_fullscreen(&rect(0, 0, 1920, 1040), &monitor, false, false));
+    }
+
+    #[test]
+    fn workspace_rect_shifts_by_a_top_or_left_taskbar() {
+        let monitor = rect(0, 0, 1920, 1080);
+        let placed = rect(100, 100, 900, 700);
+        assert_eq!(workspace_to_screen(&placed, &monitor, &rect(0, 48, 1920, 1080)), rect(100, 148, 900, 748));
+        assert_eq!(workspace_to_screen(&placed, &monitor, &rect(60, 0, 1920, 1080)), rect(160, 100, 960, 700));
+        // Bottom taskbar: workspace and screen coordinates agree
+        assert_eq!(workspace_to_screen(&placed, &monitor, &rect(0, 0, 1920, 1032)), placed);
+    }
+
+    #[test]
+    fn snap_on_secondary_monitor_with_offset_work_area() {
+        let work = rect(-1280, 40, 0, 1024);
+        assert!(is_snapped(&rect(-1280, 40, -640, 1024), &work));
+        assert!(!is_snapped(&rect(0, 0, 960, 1040), &work));
+    }
+}
@@ -0,0 +1,299 @@
+use std::time::{Duration, Instant};
+
+use windows::Win32::Foundation::RECT;
+
+use crate::config::Easing;
+
+/// Map linear progress `t` (clamped to 0..=1) through an easing curve.
+pub fn ease(easing: Easing, t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    match easing {
+        Easing::Linear => t,
+        Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+        Easing::EaseInOut => {
+            if t < 0.5 {
+                4.0 * t * t * t
+            } else {
+                1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+            }
+        }
+        Easing::EaseOutBack => {
+            const C1: f32 = 1.70158;
+            const C3: f32 = C1 + 1.0;
+            1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
+        }
+    }
+}
+
+/// Interpolate between two integers. `t` may exceed 0..=1 (overshooting curves).
+pub fn lerp(a: i32, b: i32, t: f32) -> i32 {
+    a + ((b - a) as f32 * t).round() as i32
+}
+
+/// Interpolate each edge of a rect independently.
+pub fn lerp_rect(from: &RECT, to: &RECT, t: f32) -> RECT {
+    RECT {
+        left: lerp(from.left, to.left, t),
+        top: lerp(from.top, to.top, t),
+        right: lerp(from.right, to.right, t),
+        bottom: lerp(from.bottom, to.bottom, t),
+    }
+}
+
+/// Total length of the focus-change pulse.
+pub const PULSE_DURATION: Duration = Duration::from_millis(600);
+/// One full breathing cycle (bright → dim → bright).
+pub const BREATHING_PERIOD: Duration = Duration::from_millis(3000);
+/// Lowest opacity reached by the pulse and breathing styles.
+pub const MIN_EFFECT_ALPHA: f32 = 0.35;
+
+/// Opacity of the pulse style `elapsed` after a focus change: two quick
+/// dips starting and ending fully opaque, then solid.
+pub fn pulse_alpha(elapsed: Duration) -> f32 {
+    if elapsed >= PULSE_DURATION {
+        return 1.0;
+    }
+    let p = elapsed.as_secs_f32() / PULSE_DURATION.as_secs_f32();
+    let dip = (std::f32::consts::TAU * p).sin().powi(2);
+    1.0 - (1.0 - MIN_EFFECT_ALPHA) * dip
+}
+
+/// Opacity of the breathing style `elapsed` after it was shown.
+/// A cosine cycle between 1.0 and `MIN_EFFECT_ALPHA`, starting bright.
+pub fn breathing_alpha(elapsed: Duration) -> f32 {
+    let phase = elapsed.as_secs_f32() / BREATHING_PERIOD.as_secs_f32();
+    let wave = (std::f32::consts::TAU * phase).cos() * 0.5 + 0.5; // 1 → 0 → 1
+    MIN_EFFECT_ALPHA + (1.0 - MIN_EFFECT_ALPHA) * wave
+}
+
+/// Opacity of a flash `elapsed` into its fade-out over `duration`: lingers
+/// near full strength, then eases down to 0.
+pub fn fade_alpha(elapsed: Duration, duration: Duration) -> f32 {
+    if duration.is_zero() {
+        return 0.0;
+    }
+    let t = elapsed.as_secs_f32() / duration.as_secs_f32();
+    1.0 - ease(Easing::EaseInOut, t)
+}
+
+/// Opacity `elapsed` into something shown for `duration` whose last `fade`
+/// fades out; a fade longer than the duration covers all of it.
+pub fn hold_then_fade_alpha(elapsed: Duration, duration: Duration, fade: Duration) -> f32 {
+    let fade = fade.min(duration);
+    let hold = duration - fade;
+    if elapsed < hold {
+        return 1.0;
+    }
+    fade_alpha(elapsed - hold, fade)
+}
+
+/// A rect animating from one position to another over a fixed duration.
+#[derive(Debug, Clone)]
+pub struct Transition {
+    from: RECT,
+    to: RECT,
+    start: Instant,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl Transition {
+    pub fn new(from: RECT, to: RECT, start: Instant, duration: Duration, easing: Easing) -> Self {
+        Self { from, to, start, duration, easing }
+    }
+
+    /// Linear progress in 0..=1 at `now`.
+    fn progress(&self, now: Instant) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        let elapsed = now.saturating_duration_since(self.start);
+        (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    /// Rect at `now`; equals the target once the transition has finished.
+    pub fn sample(&self, now: Instant) -> RECT {
+        let t = self.progress(now);
+        if t >= 1.0 {
+            return self.to;
+        }
+        lerp_rect(&self.from, &self.to, ease(self.easing, t))
+    }
+
+    pub fn is_finished(&self, now: Instant) -> bool {
+        self.progress(now) >= 1.0
+    }
+
+    /// Redirect a running transition (e.g. the target window moved mid-flight).
+    /// Continues from the current position so there is no visible jump.
+    pub fn retarget(&mut self, to: RECT, now: Instant) {
+        if to == self.to {
+            return;
+        }
+        let elapsed = now.saturating_duration_since(self.start);
+        self.from = self.sample(now);
+        self.to = to;
+        self.start = now;
+        self.duration = self.duration.saturating_sub(elapsed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> RECT {
+        RECT { left, top, right, bottom }
+    }
+
+    #[test]
+    fn easing_endpoints() {
+        for easing in Easing::ALL {
+            assert!(ease(easing, 0.0).abs() < 1e-5, "{:?} at 0", easing);
+            assert!((ease(easing, 1.0) - 1.0).abs() < 1e-5, "{:?} at 1", easing);
+        }
+    }
+
+    #[test]
+    fn easing_clamps_input() {
+        assert_eq!(ease(Easing::Linear, -0.5), 0.0);
+        assert_eq!(ease(Easing::Linear, 1.5), 1.0);
+    }
+
+    #[test]
+    fn ease_out_is_ahead_of_linear() {
+        for i in 1..10 {
+            let t = i as f32 / 10.0;
+            assert!(ease(Easing::EaseOut, t) > t);
+        }
+    }
+
+    #[test]
+    fn ease_in_out_is_symmetric() {
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            let sum = ease(Easing::EaseInOut, t) + ease(Easing::EaseInOut, 1.0 - t);
+            assert!((sum - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn ease_out_back_overshoots() {
+        assert!((0..100).any(|i| ease(Easing::EaseOutBack, i as f32 / 100.0) > 1.0));
+    }
+
+    #[test]
+    fn pulse_starts_and_settles_opaque() {
+        assert!((pulse_alpha(Duration::ZERO) - 1.0).abs() < 1e-5);
+        assert_eq!(pulse_alpha(PULSE_DURATION), 1.0);
+        assert_eq!(pulse_alpha(PULSE_DURATION * 2), 1.0);
+        let dip = pulse_alpha(PULSE_DURATION / 4);
+        assert!((dip - MIN_EFFECT_ALPHA).abs() < 1e-3);
+    }
+
+    #[test]
+    fn breathing_cycles_within_range() {
+        assert!((breathing_alpha(Duration::ZERO) - 1.0).abs() < 1e-5);
+        assert!((breathing_alpha(BREATHING_PERIOD / 2) - MIN_EFFECT_ALPHA).abs() < 1e-3);
+        for i in 0..50 {
+            let a = breathing_alpha(BREATHING_PERIOD * i / 20);
+            assert!((MIN_EFFECT_ALPHA - 1e-5..=1.0 + 1e-5).contains(&a));
+        }
+    }
+
+    #[test]
+    fn fade_runs_from_opaque_to_clear() {
+        let duration = Duration::from_millis(300);
+        assert_eq!(fade_alpha(Duration::ZERO, duration), 1.0);
+        assert_eq!(fade_alpha(duration, duration), 0.0);
+        assert_eq!(fade_alpha(duration * 2, duration), 0.0);
+        assert_eq!(fade_alpha(Duration::ZERO, Duration::ZERO), 0.0);
+        let mut prev = 1.0;
+        for i in 1..=10 {
+            let a = fade_alpha(duration * i / 10, duration);
+            assert!(a <= prev);
+            prev = a;
+        }
+    }
+
+    #[test]
+    fn hold_then_fade_keeps_the_total_duration() {
+        let duration = Duration::from_millis(400);
+        let fade = Duration::from_millis(100);
+        assert_eq!(hold_then_fade_alpha(Duration::ZERO, duration, fade), 1.0);
+        assert_eq!(hold_then_fade_alpha(Duration::from_millis(299), duration, fade), 1.0);
+        let mid = hold_then_fade_alpha(Duration::from_millis(350), duration, fade);
+        assert!(mid > 0.0 && mid < 1.0);
+        assert_eq!(hold_then_fade_alpha(duration, duration, fade), 0.0);
+    }
+
+    #[test]
+    fn fade_longer_than_duration_fades_throughout() {
+        let duration = Duration::from_millis(150);
+        for ms in [0, 50, 100, 150] {
+            let elapsed = Duration::from_millis(ms);
+            assert_eq!(
+                hold_then_fade_alpha(elapsed, duration, Duration::from_secs(1)),
+                fade_alpha(elapsed, duration)
+            );
+        }
+        // No fade: full strength until the flash is hidden
+        assert_eq!(hold_then_fade_alpha(Duration::from_millis(149), duration, Duration::ZERO), 1.0);
+    }
+
+    #[test]
+    fn lerp_rect_midpoint() {
+        let from = rect(0, 0, 100, 100);
+        let to = rect(100, 50, 300, 250);
+        assert_eq!(lerp_rect(&from, &to, 0.5), rect(50, 25, 200, 175));
+        assert_eq!(lerp_rect(&from, &to, 0.0), from);
+        assert_eq!(lerp_rect(&from, &to, 1.0), to);
+    }
+
+    #[test]
+    fn lerp_handles_negative_coordinates() {
+        assert_eq!(lerp(-1920, 0, 0.5), -960);
+        assert_eq!(lerp(0, -1920, 0.25), -480);
+    }
+
+    #[test]
+    fn transition_samples_over_time() {
+        let start = Instant::now();
+        let from = rect(0, 0, 100, 100);
+        let to = rect(200, 0, 300, 100);
+        let tr = Transition::new(from, to, start, Duration::from_millis(100), Easing::Linear);
+
+        assert_eq!(tr.sample(start), from);
+        assert_eq!(tr.sample(start + Duration::from_millis(50)), rect(100, 0, 200, 100));
+        assert!(!tr.is_finished(start + Duration::from_millis(50)));
+        assert_eq!(tr.sample(start + Duration::from_millis(150)), to);
+        assert!(tr.is_finished(start + Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn zero_duration_finishes_immediately() {
+        let start = Instant::now();
+        let to = rect(10, 10, 20, 20);
+        let tr = Transition::new(rect(0, 0, 5, 5), to, start, Duration::ZERO, Easing::EaseOut);
+        assert!(tr.is_finished(start));
+        assert_eq!(tr.sample(start), to);
+    }
+
+    #[test]
+    fn retarget_continues_from_current_position() {
+        let start = Instant::now();
+        let mut tr = Transition::new(
+            rect(0, 0, 100, 100),
+            rect(200, 0, 300, 100),
+            start,
+            Duration::from_millis(100),
+            Easing::Linear,
+        );
+        let mid = start + Duration::from_millis(50);
+        tr.retarget(rect(400, 0, 500, 100), mid);
+
+        assert_eq!(tr.sample(mid), rect(100, 0, 200, 100));
+        assert_eq!(tr.sample(mid + Duration::from_millis(25)), rect(250, 0, 350, 100));
+        assert!(tr.is_finished(mid + Duration::from_millis(50)));
+    }
+}
@@ -1,33 +1,124 @@
+use std::time::{Duration, Instant};
+
 use windows::Win32::Foundation::{HWND, RECT};
 use windows::Win32::Graphics::Direct2D::Common::{
     D2D_RECT_F, D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_COLOR_F, D2D1_PIXEL_FORMAT,
 };
 use windows::Win32::Graphics::Direct2D::{
-    D2D1CreateFactory, ID2D1Factory, ID2D1HwndRenderTarget,
-    D2D1_FACTORY_TYPE_SINGLE_THREADED, D2D1_HWND_RENDER_TARGET_PROPERTIES,
+    D2D1CreateFactory, ID2D1Brush, ID2D1Factory, ID2D1HwndRenderTarget, ID2D1RenderTarget,
+    D2D1_ANTIALIAS_MODE_ALIASED,
+    D2D1_CAP_STYLE_FLAT, D2D1_CAP_STYLE_ROUND, D2D1_DASH_STYLE_CUSTOM,
+    D2D1_FACTORY_TYPE_SINGLE_THREADED, D2D1_LINE_JOIN_ROUND, D2D1_ROUNDED_RECT,
+    D2D1_STROKE_STYLE_PROPERTIES, D2D1_HWND_RENDER_TARGET_PROPERTIES,
     D2D1_PRESENT_OPTIONS_IMMEDIATELY, D2D1_RENDER_TARGET_PROPERTIES,
     D2D1_RENDER_TARGET_TYPE_DEFAULT,
 };
 use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8A8_UNORM;
 
-use crate::config::{BorderColor, BorderStyle};
+use crate::config::{BorderColor, BorderStyle, Easing, GlowFalloff, RenderMode};
+use crate::overlay::animation::{self, Transition};
+use crate::overlay::surface::{self, LayeredSurface};
 use crate::overlay::window;
 
-const GLOW_LAYERS: usize = 4;
+/// Strength of glow layer `layer` (0 = outermost) of `layers`; the innermost is 1.0.
+fn glow_intensity(falloff: GlowFalloff, layer: u32, layers: u32) -> f32 {
+    let p = (layer + 1) as f32 / layers.max(1) as f32;
+    match falloff {
+        GlowFalloff::Linear => p,
+        GlowFalloff::Quadratic => p * p,
+        GlowFalloff::Gaussian => (-((1.0 - p) * 2.5).powi(2)).exp(),
+    }
+}
 
 /// Glow layer definitions: (thickness_px, color)
 /// Outermost → innermost, each layer draws a frame at that offset.
-fn glow_colors(base: &BorderColor) -> [(f32, D2D1_COLOR_F); GLOW_LAYERS] {
-    [
-        (2.0, D2D1_COLOR_F { r: base.r * 0.15, g: base.g * 0.15, b: base.b * 0.15, a: 1.0 }),
-        (2.0, D2D1_COLOR_F { r: base.r * 0.35, g: base.g * 0.35, b: base.b * 0.35, a: 1.0 }),
-        (2.0, D2D1_COLOR_F { r: base.r * 0.65, g: base.g * 0.65, b: base.b * 0.65, a: 1.0 }),
-        (2.0, D2D1_COLOR_F { r: base.r, g: base.g, b: base.b, a: 1.0 }),
-    ]
+/// With per-pixel alpha the layers fade to transparent; under the color key
+/// they can only fade toward black.
+fn glow_colors(
+    base: &BorderColor,
+    width: f32,
+    layers: u32,
+    falloff: GlowFalloff,
+    per_pixel: bool,
+) -> Vec<(f32, D2D1_COLOR_F)> {
+    let layers = layers.max(1);
+    let layer_t = width / layers as f32;
+    (0..layers)
+        .map(|i| {
+            let k = glow_intensity(falloff, i, layers);
+            let color = if per_pixel {
+                D2D1_COLOR_F { r: base.r, g: base.g, b: base.b, a: k }
+            } else {
+                D2D1_COLOR_F { r: base.r * k, g: base.g * k, b: base.b * k, a: 1.0 }
+            };
+            (layer_t, color)
+        })
+        .collect()
 }
 
-fn glow_total_thickness() -> f32 {
-    GLOW_LAYERS as f32 * 2.0 // 8px total
+/// Length of one solid-colored slice of the gradient frame.
+const GRADIENT_SEGMENT: f32 = 6.0;
+
+/// Color at `pos` (0..1, wrapping) of a cyclic gradient through evenly spaced
+/// stops — the last stop blends back into the first so the frame has no seam.
+fn gradient_color_at(stops: &[BorderColor], pos: f32) -> D2D1_COLOR_F {
+    let Some(first) = stops.first() else {
+        return D2D1_COLOR_F { r: 0.0, g: 0.0, b: 0.0, a: 1.0 };
+    };
+    if stops.len() == 1 {
+        return D2D1_COLOR_F { r: first.r, g: first.g, b: first.b, a: first.a };
+    }
+
+    let scaled = pos.rem_euclid(1.0) * stops.len() as f32;
+    let i = (scaled as usize).min(stops.len() - 1);
+    let frac = scaled - i as f32;
+    let a = &stops[i];
+    let b = &stops[(i + 1) % stops.len()];
+    D2D1_COLOR_F {
+        r: a.r + (b.r - a.r) * frac,
+        g: a.g + (b.g - a.g) * frac,
+        b: a.b + (b.b - a.b) * frac,
+        a: a.a + (b.a - a.a) * frac,
+    }
+}
+
+/// Split a frame of thickness `t` into slices for per-slice coloring.
+/// `corner` is the size of the rounded corner squares (0 for square frames);
+/// slices inside them are flagged so they can be drawn clipped to the ring.
+fn frame_segments(w: f32, h: f32, t: f32, corner: f32) -> Vec<(D2D_RECT_F, bool)> {
+    let mut segments = Vec::new();
+    let mut push_run = |from: f32, to: f32, horizontal: bool, fixed: (f32, f32), clipped: bool| {
+        let mut p = from;
+        while p < to {
+            let end = (p + GRADIENT_SEGMENT).min(to);
+            let rect = if horizontal {
+                D2D_RECT_F { left: p, top: fixed.0, right: end, bottom: fixed.1 }
+            } else {
+                D2D_RECT_F { left: fixed.0, top: p, right: fixed.1, bottom: end }
+            };
+            segments.push((rect, clipped));
+            p = end;
+        }
+    };
+
+    if corner > 0.0 {
+        // Straight edges between the corner squares
+        push_run(corner, w - corner, true, (0.0, t), false);
+        push_run(corner, w - corner, true, (h - t, h), false);
+        push_run(corner, h - corner, false, (0.0, t), false);
+        push_run(corner, h - corner, false, (w - t, w), false);
+        // Corner squares, sliced vertically
+        push_run(0.0, corner, true, (0.0, corner), true);
+        push_run(w - corner, w, true, (0.0, corner), true);
+        push_run(0.0, corner, true, (h - corner, h), true);
+        push_run(w - corner, w, true, (h - corner, h), true);
+    } else {
+        push_run(0.0, w, true, (0.0, t), false);
+        push_run(0.0, w, true, (h - t, h), false);
+        push_run(t, h - t, false, (0.0, t), false);
+        push_run(t, h - t, false, (w - t, w), false);
+    }
+    segments
 }
 
 /// Manages the border overlay rendering via Direct2D.
@@ -38,7 +129,29 @@ pub struct BorderOverlay {
     thickness: f32,
     color: BorderColor,
     style: BorderStyle,
+    /// Corner radius of the target window in px; 0 draws a square frame.
+    corner_radius: f32,
     last_overlay_rect: RECT,
+    /// Window rect the border currently surrounds (excluding thickness).
+    last_target_rect: RECT,
+    /// Slide duration for focus changes; zero disables the animation.
+    animation_duration: Duration,
+    easing: Easing,
+    transition: Option<Transition>,
+    /// When the current Pulse/Breathing/rotation effect started; None while idle.
+    effect_start: Option<Instant>,
+    gradient_stops: Vec<BorderColor>,
+    gradient_rotate: bool,
+    gradient_rotation: Duration,
+    dash_length: f32,
+    dash_gap: f32,
+    glow_width: f32,
+    glow_layers: u32,
+    glow_falloff: GlowFalloff,
+    render_mode: RenderMode,
+    /// Per-pixel alpha surface; while set, the window is driven by
+    /// UpdateLayeredWindow instead of the color key and `render_target`.
+    surface: Option<LayeredSurface>,
 }
 
 impl BorderOverlay {
@@ -57,25 +170,70 @@ impl BorderOverlay {
             thickness,
             color,
             style,
+            corner_radius: 0.0,
             last_overlay_rect: RECT::default(),
+            last_target_rect: RECT::default(),
+            animation_duration: Duration::ZERO,
+            easing: Easing::EaseOut,
+            transition: None,
+            effect_start: None,
+            gradient_stops: vec![color],
+            gradient_rotate: false,
+            gradient_rotation: Duration::from_millis(4000),
+            dash_length: 12.0,
+            dash_gap: 6.0,
+            glow_width: 8.0,
+            glow_layers: 4,
+            glow_falloff: GlowFalloff::Quadratic,
+            render_mode: RenderMode::ColorKey,
+            surface: None,
         })
     }
 
-    fn effective_thickness(&self) -> f32 {
+    /// How far the border extends outside the window, in px.
+    pub fn effective_thickness(&self) -> f32 {
         match self.style {
-            BorderStyle::Solid => self.thickness,
-            BorderStyle::Glow => glow_total_thickness(),
+            BorderStyle::Glow => self.glow_width,
+            _ => self.thickness,
         }
     }
 
+    fn overlay_rect(&self, target_rect: &RECT) -> RECT {
+        // Round up so a fractional width isn't clipped at the outer edge
+        let t = self.effective_thickness().ceil() as i32;
+        RECT {
+            left: target_rect.left - t,
+            top: target_rect.top - t,
+            right: target_rect.right + t,
+            bottom: target_rect.bottom + t,
+        }
+    }
+
+    /// Switch between per-pixel alpha and the color-key fallback.
+    /// Takes effect on the next `update`/`move_to`.
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        if self.render_mode == mode {
+            return;
+        }
+        self.render_mode = mode;
+        self.render_target = None;
+        self.surface = surface::attach(self.hwnd, &self.factory, mode == RenderMode::PerPixel);
+        self.last_overlay_rect = RECT::default();
+    }
+
     pub fn set_style(&mut self, style: BorderStyle) {
         if self.style != style {
             self.style = style;
             self.last_overlay_rect = RECT::default();
+            // The next move_to restarts the effect for the new style
+            self.effect_start = None;
         }
     }
 
     pub fn set_color(&mut self, color: BorderColor) {
+        if self.color == color {
+            return;
+        }
         self.color = color;
         self.last_overlay_rect = RECT::default();
     }
@@ -87,6 +245,149 @@ impl BorderOverlay {
         }
     }
 
+    /// Configure the colors and rotation of the Gradient style.
+    pub fn set_gradient(&mut self, stops: &[BorderColor], rotate: bool, rotation_ms: u32) {
+        self.gradient_stops = stops.to_vec();
+        self.gradient_rotate = rotate;
+        self.gradient_rotation = Duration::from_millis(rotation_ms.max(1) as u64);
+        if self.style == BorderStyle::Gradient {
+            self.last_overlay_rect = RECT::default();
+            self.effect_start = None;
+        }
+    }
+
+    /// Configure the dash pattern (in px) of the Dashed and Dotted styles.
+    pub fn set_dash_pattern(&mut self, dash_length: f32, dash_gap: f32) {
+        self.dash_length = dash_length;
+        self.dash_gap = dash_gap;
+        if matches!(self.style, BorderStyle::Dashed | BorderStyle::Dotted) {
+            self.last_overlay_rect = RECT::default();
+        }
+    }
+
+    /// Configure the width (px), layer count and falloff of the Glow style.
+    pub fn set_glow(&mut self, width: f32, layers: u32, falloff: GlowFalloff) {
+        self.glow_width = width.max(1.0);
+        self.glow_layers = layers.max(1);
+        self.glow_falloff = falloff;
+        if self.style == BorderStyle::Glow {
+            self.last_overlay_rect = RECT::default();
+        }
+    }
+
+    /// Configure the slide animation used by `move_to`.
+    pub fn set_animation(&mut self, enabled: bool, duration_ms: u32, easing: Easing) {
+        self.animation_duration = if enabled {
+            Duration::from_millis(duration_ms as u64)
+        } else {
+            Duration::ZERO
+        };
+        self.easing = easing;
+    }
+
+    /// Whether a transition or alpha effect is running and needs `tick` calls.
+    pub fn is_animating(&self) -> bool {
+        self.transition.is_some() || self.effect_start.is_some()
+    }
+
+    /// Advance running animations by one frame.
+    /// Returns false once there is nothing left to animate.
+    pub fn tick(&mut self) -> bool {
+        if !self.is_animating() {
+            return false;
+        }
+
+        // Hidden mid-flight (focus went to the desktop etc.) — drop the animation
+        if !window::is_visible(self.hwnd) {
+            self.transition = None;
+            self.effect_start = None;
+            return false;
+        }
+
+        let now = Instant::now();
+
+        if let Some(transition) = &self.transition {
+            let rect = transition.sample(now);
+            let finished = transition.is_finished(now);
+            self.apply_rect(&rect);
+            if finished {
+                self.transition = None;
+            }
+        }
+
+        if let Some(start) = self.effect_start {
+            match self.style {
+                BorderStyle::Gradient => self.redraw(),
+                BorderStyle::Pulse
+                    if now.saturating_duration_since(start) >= animation::PULSE_DURATION =>
+                {
+                    self.effect_start = None;
+                    self.show_content();
+                }
+                _ => self.show_content(),
+            }
+        }
+
+        self.is_animating()
+    }
+
+    /// Opacity the Pulse/Breathing effects want right now (1.0 otherwise).
+    fn effect_alpha(&self) -> f32 {
+        let Some(start) = self.effect_start else { return 1.0 };
+        let elapsed = Instant::now().saturating_duration_since(start);
+        match self.style {
+            BorderStyle::Pulse => animation::pulse_alpha(elapsed),
+            BorderStyle::Breathing => animation::breathing_alpha(elapsed),
+            _ => 1.0,
+        }
+    }
+
+    /// Window opacity: the border color's alpha scaled by the effect opacity.
+    /// Applied to the whole window so it also works under the color key,
+    /// where translucent pixels would blend with the magenta background.
+    fn content_alpha(&self) -> f32 {
+        self.color.a.clamp(0.0, 1.0) * self.effect_alpha()
+    }
+
+    /// Make rendered pixels visible, applying the current opacity.
+    fn show_content(&self) {
+        let alpha = self.content_alpha();
+        if let Some(surface) = &self.surface {
+            surface.present(self.hwnd, &self.last_overlay_rect, (alpha * 255.0).round() as u8);
+        } else if alpha >= 1.0 {
+            window::set_colorkey(self.hwnd);
+        } else {
+            window::set_colorkey_alpha(self.hwnd, (alpha * 255.0).round() as u8);
+        }
+    }
+
+    /// Kick off the style's alpha effect after the border moved to a new window.
+    /// Pulse restarts on every focus change; Breathing keeps its phase.
+    fn start_effect(&mut self) {
+        match self.style {
+            BorderStyle::Pulse => self.effect_start = Some(Instant::now()),
+            BorderStyle::Breathing => {
+                if self.effect_start.is_none() {
+                    self.effect_start = Some(Instant::now());
+                }
+            }
+            BorderStyle::Gradient if self.gradient_rotate => {
+                if self.effect_start.is_none() {
+                    self.effect_start = Some(Instant::now());
+                }
+            }
+            _ => self.effect_start = None,
+        }
+    }
+
+    /// Set the corner radius to match the target window's rounded corners.
+    pub fn set_corner_radius(&mut self, radius: f32) {
+        if (self.corner_radius - radius).abs() > f32::EPSILON {
+            self.corner_radius = radius;
+            self.last_overlay_rect = RECT::default();
+        }
+    }
+
     fn create_render_target(&mut self) {
         unsafe {
             let mut client_rect = RECT::default();
@@ -119,14 +420,19 @@ impl BorderOverlay {
     }
 
     /// Update overlay position and redraw border around the target rect.
+    /// While a transition is running, the new rect becomes its destination.
     pub fn update(&mut self, target_rect: &RECT) {
-        let t = self.effective_thickness() as i32;
-        let overlay_rect = RECT {
-            left: target_rect.left - t,
-            top: target_rect.top - t,
-            right: target_rect.right + t,
-            bottom: target_rect.bottom + t,
-        };
+        if let Some(ref mut transition) = self.transition {
+            transition.retarget(*target_rect, Instant::now());
+            return;
+        }
+        self.apply_rect(target_rect);
+    }
+
+    fn apply_rect(&mut self, target_rect: &RECT) {
+        self.last_target_rect = *target_rect;
+
+        let overlay_rect = self.overlay_rect(target_rect);
 
         if overlay_rect == self.last_overlay_rect {
             return;
@@ -138,6 +444,18 @@ impl BorderOverlay {
         let new_h = overlay_rect.bottom - overlay_rect.top;
         let size_changed = new_w != old_w || new_h != old_h;
 
+        if self.surface.is_some() {
+            // The per-pixel frame is swapped in atomically with the move,
+            // so no alpha=0 protection is needed.
+            self.last_overlay_rect = overlay_rect;
+            if !size_changed {
+                window::reposition_overlay(self.hwnd, &overlay_rect);
+            } else if !self.render_layered(&overlay_rect) {
+                window::hide_overlay(self.hwnd);
+            }
+            return;
+        }
+
         if size_changed {
             // Alpha=0 protection: surface is invalidated on resize,
             // hide content until re-render completes to avoid black flash.
@@ -151,7 +469,7 @@ impl BorderOverlay {
             self.render_target = None;
             self.create_render_target();
             if self.render(&overlay_rect) {
-                window::set_colorkey(self.hwnd);
+                self.show_content();
             } else {
                 // Render failed — hide overlay instead of showing black
                 window::hide_overlay(self.hwnd);
@@ -167,18 +485,54 @@ impl BorderOverlay {
     /// 3. D2D render (window is visible to system, render works correctly)
     /// 4. Restore colorkey-only mode — rendered content becomes visible
     /// 5. Bring to front
+    ///
+    /// With animation enabled and the border already showing, the border
+    /// instead slides from its current rect to the new one (see `tick`).
     pub fn move_to(&mut self, target_rect: &RECT) {
+        if !self.animation_duration.is_zero()
+            && window::is_visible(self.hwnd)
+            && self.last_target_rect != RECT::default()
+        {
+            let now = Instant::now();
+            let from = match &self.transition {
+                Some(transition) => transition.sample(now),
+                None => self.last_target_rect,
+            };
+            if from != *target_rect {
+                self.transition = Some(Transition::new(
+                    from,
+                    *target_rect,
+                    now,
+                    self.animation_duration,
+                    self.easing,
+                ));
+                window::bring_to_front(self.hwnd);
+                self.start_effect();
+                return;
+            }
+        }
+        self.transition = None;
+        self.start_effect();
+
+        let overlay_rect = self.overlay_rect(target_rect);
+
+        // Per-pixel surfaces render off-screen and present frame and
+        // position in one call, so none of the steps below are needed.
+        if self.surface.is_some() {
+            if self.render_layered(&overlay_rect) {
+                self.last_overlay_rect = overlay_rect;
+                self.last_target_rect = *target_rect;
+                window::bring_to_front(self.hwnd);
+            } else {
+                window::hide_overlay(self.hwnd);
+            }
+            return;
+        }
+
         // Step 1: alpha=0 — fully invisible but D2D still functional
         window::set_fully_transparent(self.hwnd);
 
-        // Step 2: Calculate new overlay rect and reposition
-        let t = self.effective_thickness() as i32;
-        let overlay_rect = RECT {
-            left: target_rect.left - t,
-            top: target_rect.top - t,
-            right: target_rect.right + t,
-            bottom: target_rect.bottom + t,
-        };
+        // Step 2: Reposition to the new overlay rect
         window::reposition_overlay(self.hwnd, &overlay_rect);
 
         // Step 3: Recreate render target at new size and render
@@ -186,11 +540,12 @@ impl BorderOverlay {
         self.create_render_target();
 
         if self.render(&overlay_rect) {
-            // Step 4: Restore colorkey-only (non-magenta pixels become visible)
-            window::set_colorkey(self.hwnd);
+            // Step 4: Restore colorkey (non-magenta pixels become visible)
+            self.show_content();
 
             // Step 5: Update state and bring to front
             self.last_overlay_rect = overlay_rect;
+            self.last_target_rect = *target_rect;
             window::bring_to_front(self.hwnd);
         } else {
             // Render failed — hide overlay instead of showing black
@@ -198,6 +553,40 @@ impl BorderOverlay {
         }
     }
 
+    /// Re-render at the current position (rotating gradient frames).
+    fn redraw(&mut self) {
+        let rect = self.last_overlay_rect;
+        if rect == RECT::default() {
+            return;
+        }
+        let rendered = if self.surface.is_some() {
+            self.render_layered(&rect)
+        } else {
+            self.render(&rect)
+        };
+        if !rendered {
+            window::hide_overlay(self.hwnd);
+        }
+    }
+
+    /// Draw into the per-pixel surface and present it at `overlay_rect`
+    /// with the current opacity.
+    fn render_layered(&mut self, overlay_rect: &RECT) -> bool {
+        let Some(mut surface) = self.surface.take() else { return false };
+
+        let w = (overlay_rect.right - overlay_rect.left) as f32;
+        let h = (overlay_rect.bottom - overlay_rect.top) as f32;
+        let alpha = (self.content_alpha() * 255.0).round() as u8;
+
+        let drawn = surface.draw(w as i32, h as i32, |rt| unsafe {
+            self.draw_style(rt, w, h, true);
+        });
+        let presented = drawn && surface.present(self.hwnd, overlay_rect, alpha);
+
+        self.surface = Some(surface);
+        presented
+    }
+
     fn render(&self, overlay_rect: &RECT) -> bool {
         let Some(rt) = &self.render_target else { return false };
 
@@ -210,16 +599,41 @@ impl BorderOverlay {
             let clear_color = D2D1_COLOR_F { r: 1.0, g: 0.0, b: 1.0, a: 1.0 };
             rt.Clear(Some(&clear_color));
 
-            match self.style {
-                BorderStyle::Solid => self.render_solid(rt, w, h),
-                BorderStyle::Glow => self.render_glow(rt, w, h),
-            }
+            // Anti-aliased edges would blend with the magenta color key
+            // and leave a pink fringe around rounded corners.
+            rt.SetAntialiasMode(D2D1_ANTIALIAS_MODE_ALIASED);
+
+            self.draw_style(rt, w, h, false);
 
             rt.EndDraw(None, None).is_ok()
         }
     }
 
-    unsafe fn render_solid(&self, rt: &ID2D1HwndRenderTarget, w: f32, h: f32) {
+    /// Draw the current style. `per_pixel` is set when the target has a real
+    /// alpha channel instead of the magenta color key.
+    unsafe fn draw_style(&self, rt: &ID2D1RenderTarget, w: f32, h: f32, per_pixel: bool) {
+        match self.style {
+            BorderStyle::Glow => self.render_glow(rt, w, h, per_pixel),
+            BorderStyle::Gradient => self.render_gradient(rt, w, h, per_pixel),
+            BorderStyle::Dashed | BorderStyle::Dotted => self.render_dashed(rt, w, h),
+            // Pulse/Breathing animate window alpha over a solid frame
+            BorderStyle::Solid | BorderStyle::Pulse | BorderStyle::Breathing => {
+                self.render_solid(rt, w, h)
+            }
+        }
+    }
+
+    /// Corner radius of a band centerline lying `distance` px outside the
+    /// window edge, or 0 when the window has square corners.
+    fn band_radius(&self, distance: f32) -> f32 {
+        if self.corner_radius > 0.0 {
+            self.corner_radius + distance
+        } else {
+            0.0
+        }
+    }
+
+    unsafe fn render_solid(&self, rt: &ID2D1RenderTarget, w: f32, h: f32) {
         let t = self.thickness;
         let color = D2D1_COLOR_F {
             r: self.color.r,
@@ -229,50 +643,274 @@ impl BorderOverlay {
         };
         let Ok(brush) = rt.CreateSolidColorBrush(&color, None) else { return };
 
-        rt.FillRectangle(&D2D_RECT_F { left: 0.0, top: 0.0, right: w, bottom: t }, &brush);
-        rt.FillRectangle(&D2D_RECT_F { left: 0.0, top: h - t, right: w, bottom: h }, &brush);
-        rt.FillRectangle(&D2D_RECT_F { left: 0.0, top: t, right: t, bottom: h - t }, &brush);
-        rt.FillRectangle(&D2D_RECT_F { left: w - t, top: t, right: w, bottom: h - t }, &brush);
+        draw_frame(rt, &brush, w, h, 0.0, t, self.band_radius(t / 2.0));
     }
 
-    unsafe fn render_glow(&self, rt: &ID2D1HwndRenderTarget, w: f32, h: f32) {
-        let layers = glow_colors(&self.color);
-        let mut offset: f32 = 0.0;
+    unsafe fn render_glow(&self, rt: &ID2D1RenderTarget, w: f32, h: f32, per_pixel: bool) {
+        let layers = glow_colors(
+            &self.color,
+            self.glow_width,
+            self.glow_layers,
+            self.glow_falloff,
+            per_pixel,
+        );
+        // The overlay margin is the width rounded up; the glow hugs the window
+        let total = self.glow_width.ceil();
+        let mut offset: f32 = total - self.glow_width;
 
         for (layer_t, color) in &layers {
             let Ok(brush) = rt.CreateSolidColorBrush(color, None) else { return };
 
-            let inner_top = offset;
-            let inner_bottom = offset + layer_t;
-            let outer_w = w - offset;
-            let outer_h = h - offset;
-
-            // Top
-            rt.FillRectangle(
-                &D2D_RECT_F { left: offset, top: inner_top, right: outer_w, bottom: inner_bottom },
-                &brush,
-            );
-            // Bottom
-            rt.FillRectangle(
-                &D2D_RECT_F { left: offset, top: outer_h - layer_t, right: outer_w, bottom: outer_h },
-                &brush,
-            );
-            // Left
-            rt.FillRectangle(
-                &D2D_RECT_F { left: offset, top: inner_bottom, right: offset + layer_t, bottom: outer_h - layer_t },
-                &brush,
-            );
-            // Right
-            rt.FillRectangle(
-                &D2D_RECT_F { left: outer_w - layer_t, top: inner_bottom, right: outer_w, bottom: outer_h - layer_t },
-                &brush,
-            );
+            let radius = self.band_radius(total - offset - layer_t / 2.0);
+            draw_frame(rt, &brush, w, h, offset, *layer_t, radius);
 
             offset += layer_t;
         }
     }
 
+    unsafe fn render_dashed(&self, rt: &ID2D1RenderTarget, w: f32, h: f32) {
+        // The dash pattern is divided by the stroke width
+        let t = self.thickness.max(1.0);
+        let color = D2D1_COLOR_F {
+            r: self.color.r,
+            g: self.color.g,
+            b: self.color.b,
+            a: 1.0,
+        };
+        let Ok(brush) = rt.CreateSolidColorBrush(&color, None) else { return };
+
+        // Dash lengths are expressed in multiples of the stroke width.
+        // A zero-length dash with round caps renders as a dot of diameter t.
+        let (cap, dashes) = if self.style == BorderStyle::Dotted {
+            (D2D1_CAP_STYLE_ROUND, [0.0, (self.dash_gap + t) / t])
+        } else {
+            (D2D1_CAP_STYLE_FLAT, [self.dash_length.max(1.0) / t, self.dash_gap.max(1.0) / t])
+        };
+        let props = D2D1_STROKE_STYLE_PROPERTIES {
+            startCap: cap,
+            endCap: cap,
+            dashCap: cap,
+            lineJoin: D2D1_LINE_JOIN_ROUND,
+            miterLimit: 10.0,
+            dashStyle: D2D1_DASH_STYLE_CUSTOM,
+            dashOffset: 0.0,
+        };
+        let Ok(stroke) = self.factory.CreateStrokeStyle(&props, Some(&dashes)) else { return };
+
+        let half = t / 2.0;
+        let rect = D2D_RECT_F { left: half, top: half, right: w - half, bottom: h - half };
+        let radius = self.band_radius(half);
+        if radius > 0.0 {
+            let rounded = D2D1_ROUNDED_RECT { rect, radiusX: radius, radiusY: radius };
+            rt.DrawRoundedRectangle(&rounded, &brush, t, &stroke);
+        } else {
+            rt.DrawRectangle(&rect, &brush, t, &stroke);
+        }
+    }
+
+    /// Stop alphas need per-pixel alpha; under the color key they would
+    /// blend with the magenta background, so the slices are drawn opaque.
+    unsafe fn render_gradient(&self, rt: &ID2D1RenderTarget, w: f32, h: f32, per_pixel: bool) {
+        let t = self.thickness;
+        let color_at = |pos: f32| {
+            let color = gradient_color_at(&self.gradient_stops, pos);
+            if per_pixel {
+                color
+            } else {
+                D2D1_COLOR_F { a: 1.0, ..color }
+            }
+        };
+        let first = color_at(0.0);
+        let Ok(brush) = rt.CreateSolidColorBrush(&first, None) else { return };
+
+        let phase = match self.effect_start {
+            Some(start) => {
+                let elapsed = Instant::now().saturating_duration_since(start);
+                elapsed.as_secs_f32() / self.gradient_rotation.as_secs_f32()
+            }
+            None => 0.0,
+        };
+
+        // Corner squares are drawn by clipping a stroked ring, so the
+        // slices follow the rounded outline instead of poking out square.
+        let corner = if self.corner_radius > 0.0 { self.corner_radius + t } else { 0.0 };
+        let half = t / 2.0;
+        let ring = D2D1_ROUNDED_RECT {
+            rect: D2D_RECT_F { left: half, top: half, right: w - half, bottom: h - half },
+            radiusX: self.band_radius(half),
+            radiusY: self.band_radius(half),
+        };
+
+        let (cx, cy) = (w / 2.0, h / 2.0);
+        for (rect, clipped) in frame_segments(w, h, t, corner) {
+            let mx = (rect.left + rect.right) / 2.0 - cx;
+            let my = (rect.top + rect.bottom) / 2.0 - cy;
+            // Angle around the center, clockwise from the right, as 0..1
+            let pos = my.atan2(mx) / std::f32::consts::TAU + phase;
+            brush.SetColor(&color_at(pos));
+
+            if clipped {
+                rt.PushAxisAlignedClip(&rect, D2D1_ANTIALIAS_MODE_ALIASED);
+                rt.DrawRoundedRectangle(&ring, &brush, t, None);
+                rt.PopAxisAlignedClip();
+            } else {
+                rt.FillRectangle(&rect, &brush);
+            }
+        }
+    }
+
     pub fn hide(&self) {
         window::hide_overlay(self.hwnd);
     }
 }
+
+/// Fill a frame band `thickness` px wide, starting `offset` px in from the
+/// overlay edge. `radius` is the corner radius at the band's centerline;
+/// a non-positive radius draws four plain rectangles.
+unsafe fn draw_frame(
+    rt: &ID2D1RenderTarget,
+    brush: &ID2D1Brush,
+    w: f32,
+    h: f32,
+    offset: f32,
+    thickness: f32,
+    radius: f32,
+) {
+    let outer_w = w - offset;
+    let outer_h = h - offset;
+
+    if radius <= 0.0 {
+        let inner = offset + thickness;
+        // Top
+        rt.FillRectangle(
+            &D2D_RECT_F { left: offset, top: offset, right: outer_w, bottom: inner },
+            brush,
+        );
+        // Bottom
+        rt.FillRectangle(
+            &D2D_RECT_F { left: offset, top: outer_h - thickness, right: outer_w, bottom: outer_h },
+            brush,
+        );
+        // Left
+        rt.FillRectangle(
+            &D2D_RECT_F { left: offset, top: inner, right: inner, bottom: outer_h - thickness },
+            brush,
+        );
+        // Right
+        rt.FillRectangle(
+            &D2D_RECT_F { left: outer_w - thickness, top: inner, right: outer_w, bottom: outer_h - thickness },
+            brush,
+        );
+        return;
+    }
+
+    let half = thickness / 2.0;
+    let rounded = D2D1_ROUNDED_RECT {
+        rect: D2D_RECT_F {
+            left: offset + half,
+            top: offset + half,
+            right: outer_w - half,
+            bottom: outer_h - half,
+        },
+        radiusX: radius,
+        radiusY: radius,
+    };
+    rt.DrawRoundedRectangle(&rounded, brush, thickness, None);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stops() -> Vec<BorderColor> {
+        vec![BorderColor::new(1.0, 0.0, 0.0, 1.0), BorderColor::new(0.0, 0.0, 1.0, 0.5)]
+    }
+
+    fn rgba(c: D2D1_COLOR_F) -> [f32; 4] {
+        [c.r, c.g, c.b, c.a]
+    }
+
+    fn area(r: &D2D_RECT_F) -> f32 {
+        (r.right - r.left) * (r.bottom - r.top)
+    }
+
+    #[test]
+    fn glow_falloff_rises_to_full_strength_inside() {
+        for falloff in GlowFalloff::ALL {
+            for layers in [1, 2, 4, 7, 16] {
+                let k: Vec<f32> = (0..layers).map(|i| glow_intensity(falloff, i, layers)).collect();
+                assert!(k.windows(2).all(|p| p[0] < p[1]), "{:?} with {} layers: {:?}", falloff, layers, k);
+                assert_eq!(k[layers as usize - 1], 1.0);
+                assert!(k[0] > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn glow_layers_split_the_width() {
+        let base = BorderColor::new(0.2, 0.4, 0.8, 1.0);
+        let layers = glow_colors(&base, 9.0, 3, GlowFalloff::Linear, true);
+        assert_eq!(layers.len(), 3);
+        assert_eq!(layers.iter().map(|(t, _)| t).sum::<f32>(), 9.0);
+        assert_eq!(rgba(layers[2].1), [0.2, 0.4, 0.8, 1.0]);
+        assert_eq!(glow_colors(&base, 9.0, 0, GlowFalloff::Linear, true).len(), 1);
+    }
+
+    #[test]
+    fn glow_fades_alpha_per_pixel_and_darkens_under_color_key() {
+        let base = BorderColor::new(1.0, 0.5, 0.0, 1.0);
+        let per_pixel = glow_colors(&base, 8.0, 4, GlowFalloff::Quadratic, true);
+        let keyed = glow_colors(&base, 8.0, 4, GlowFalloff::Quadratic, false);
+        assert!(per_pixel.windows(2).all(|p| p[0].1.a < p[1].1.a));
+        assert!(per_pixel.iter().all(|(_, c)| c.r == 1.0));
+        assert!(keyed.windows(2).all(|p| p[0].1.r < p[1].1.r));
+        assert!(keyed.iter().all(|(_, c)| c.a == 1.0));
+    }
+
+    #[test]
+    fn gradient_hits_stops_evenly_spaced() {
+        assert_eq!(rgba(gradient_color_at(&stops(), 0.0)), [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(rgba(gradient_color_at(&stops(), 0.5)), [0.0, 0.0, 1.0, 0.5]);
+        assert_eq!(rgba(gradient_color_at(&stops(), 0.25)), [0.5, 0.0, 0.5, 0.75]);
+    }
+
+    #[test]
+    fn gradient_wraps_back_to_first_stop() {
+        assert_eq!(rgba(gradient_color_at(&stops(), 0.75)), [0.5, 0.0, 0.5, 0.75]);
+        assert_eq!(rgba(gradient_color_at(&stops(), 1.0)), rgba(gradient_color_at(&stops(), 0.0)));
+        assert_eq!(rgba(gradient_color_at(&stops(), -0.25)), rgba(gradient_color_at(&stops(), 0.75)));
+        assert_eq!(rgba(gradient_color_at(&stops(), 2.5)), rgba(gradient_color_at(&stops(), 0.5)));
+    }
+
+    #[test]
+    fn single_stop_is_a_solid_color() {
+        let stop = BorderColor::new(0.2, 0.4, 0.6, 0.8);
+        for pos in [0.0, 0.3, 0.99] {
+            assert_eq!(rgba(gradient_color_at(&[stop], pos)), [0.2, 0.4, 0.6, 0.8]);
+        }
+        assert_eq!(gradient_color_at(&[], 0.5).a, 1.0);
+    }
+
+    #[test]
+    fn square_segments_cover_the_frame_exactly() {
+        let (w, h, t) = (200.0, 100.0, 4.0);
+        let segments = frame_segments(w, h, t, 0.0);
+        let covered: f32 = segments.iter().map(|(r, _)| area(r)).sum();
+        assert_eq!(covered, 2.0 * w * t + 2.0 * (h - 2.0 * t) * t);
+        for (r, clipped) in &segments {
+            assert!(!clipped);
+            assert!(r.left >= 0.0 && r.top >= 0.0 && r.right <= w && r.bottom <= h);
+            assert!((r.right - r.left).max(r.bottom - r.top) <= GRADIENT_SEGMENT);
+        }
+    }
+
+    #[test]
+    fn rounded_segments_cover_edges_and_corner_squares() {
+        let (w, h, t, corner) = (200.0, 100.0, 4.0, 12.0);
+        let segments = frame_segments(w, h, t, corner);
+        let covered: f32 = segments.iter().filter(|(_, c)| !c).map(|(r, _)| area(r)).sum();
+        assert_eq!(covered, 2.0 * (w - 2.0 * corner) * t + 2.0 * (h - 2.0 * corner) * t);
+        let corners: f32 = segments.iter().filter(|(_, c)| *c).map(|(r, _)| area(r)).sum();
+        assert_eq!(corners, 4.0 * corner * corner);
+    }
+}
@@ -1,54 +1,183 @@
+use std::time::{Duration, Instant};
+
 use windows::Win32::Foundation::{HWND, RECT};
 use windows::Win32::Graphics::Direct2D::Common::{
-    D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_COLOR_F, D2D1_PIXEL_FORMAT,
+    D2D_RECT_F, D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_COLOR_F, D2D1_GRADIENT_STOP,
+    D2D1_PIXEL_FORMAT,
 };
 use windows::Win32::Graphics::Direct2D::{
-    D2D1CreateFactory, ID2D1Factory,
-    D2D1_FACTORY_TYPE_SINGLE_THREADED, D2D1_HWND_RENDER_TARGET_PROPERTIES,
-    D2D1_PRESENT_OPTIONS_IMMEDIATELY, D2D1_RENDER_TARGET_PROPERTIES,
-    D2D1_RENDER_TARGET_TYPE_DEFAULT,
+    D2D1CreateFactory, ID2D1Brush, D2D1_ANTIALIAS_MODE_ALIASED, ID2D1Factory, ID2D1RenderTarget, D2D1_ELLIPSE,
+    D2D1_EXTEND_MODE_CLAMP, D2D1_FACTORY_TYPE_SINGLE_THREADED, D2D1_GAMMA_2_2,
+    D2D1_HWND_RENDER_TARGET_PROPERTIES, D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES,
+    D2D1_PRESENT_OPTIONS_IMMEDIATELY, D2D1_RADIAL_GRADIENT_BRUSH_PROPERTIES,
+    D2D1_RENDER_TARGET_PROPERTIES, D2D1_RENDER_TARGET_TYPE_DEFAULT,
 };
 use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8A8_UNORM;
 
+use crate::accent;
+use crate::config::{BorderColor, FlashShape, FlashTarget, RenderMode};
+use crate::contrast;
+use crate::overlay::animation;
+use crate::overlay::surface::{self, LayeredSurface};
 use crate::overlay::window;
 
-/// Full-screen flash overlay shown when focus moves to a different monitor.
+/// Edge band width as a fraction of the monitor's shorter side.
+const EDGE_FRACTION: f32 = 0.06;
+/// Corner glow radius as a fraction of the monitor's shorter side.
+const CORNER_FRACTION: f32 = 0.35;
+/// Strength of the monitor part when flashing both monitor and window.
+const BACKDROP_STRENGTH: f32 = 0.5;
+/// Outline drawn around the window when the color key can't tell it apart
+/// from a filled backdrop.
+const WINDOW_OUTLINE: f32 = 4.0;
+
+/// Flash over the monitor and/or window that just received focus.
+/// Hidden after the configured duration, fading out over its end, driven by `tick`.
 pub struct FlashOverlay {
     pub hwnd: HWND,
     factory: ID2D1Factory,
+    /// Per-pixel alpha surface; None while on the color key.
+    surface: Option<LayeredSurface>,
+    color: D2D1_COLOR_F,
+    shape: FlashShape,
+    opacity: f32,
+    /// Multiplier on `opacity` for the current flash (below 1 for subtle flashes).
+    strength: f32,
+    /// Window area inside the overlay when flashing monitor and window together.
+    window_area: Option<D2D_RECT_F>,
+    duration: Duration,
+    /// Fade-out at the end of `duration`.
+    fade: Duration,
+    fade_start: Option<Instant>,
 }
 
 impl FlashOverlay {
-    pub fn new(opacity: f32) -> Option<Self> {
+    pub fn new(opacity: f32, render_mode: RenderMode) -> Option<Self> {
         let hwnd = window::create_overlay_window("WhereIsMyWindowFlash", 1, 1)?;
         let factory: ID2D1Factory = unsafe {
             D2D1CreateFactory(D2D1_FACTORY_TYPE_SINGLE_THREADED, None).ok()?
         };
 
-        // Use whole-window alpha for flash transparency
-        window::set_alpha(hwnd, (opacity * 255.0) as u8);
+        let surface = surface::attach(hwnd, &factory, render_mode == RenderMode::PerPixel);
 
         Some(Self {
             hwnd,
             factory,
+            surface,
+            color: D2D1_COLOR_F { r: 0.0, g: 0.47, b: 0.84, a: 1.0 },
+            shape: FlashShape::Fill,
+            opacity,
+            strength: 1.0,
+            window_area: None,
+            duration: Duration::from_millis(150),
+            fade: Duration::from_millis(150),
+            fade_start: None,
         })
     }
 
-    /// Show flash over the given monitor rect.
-    pub fn flash(&self, monitor_rect: &RECT) {
-        window::reposition_overlay(self.hwnd, monitor_rect);
-        self.render(monitor_rect);
-        window::show_overlay(self.hwnd);
+    /// Flash `target` and start fading it out. `window_rect` should already be
+    /// clamped to `monitor_rect`; `strength` scales the configured opacity.
+    pub fn flash(
+        &mut self,
+        monitor_rect: &RECT,
+        window_rect: &RECT,
+        target: FlashTarget,
+        strength: f32,
+    ) {
+        self.strength = strength;
+        let rect = match target {
+            FlashTarget::Window => *window_rect,
+            FlashTarget::Monitor | FlashTarget::Both => *monitor_rect,
+        };
+        self.window_area = (target == FlashTarget::Both).then(|| D2D_RECT_F {
+            left: (window_rect.left - rect.left) as f32,
+            top: (window_rect.top - rect.top) as f32,
+            right: (window_rect.right - rect.left) as f32,
+            bottom: (window_rect.bottom - rect.top) as f32,
+        });
+
+        let shown = if let Some(mut layered) = self.surface.take() {
+            let w = rect.right - rect.left;
+            let h = rect.bottom - rect.top;
+            let drawn = layered.draw(w, h, |rt| unsafe { self.draw(rt, w as f32, h as f32, true) });
+            let presented = drawn && layered.present(self.hwnd, &rect, self.alpha_byte(1.0));
+            self.surface = Some(layered);
+            presented
+        } else {
+            window::reposition_overlay(self.hwnd, &rect);
+            self.render(&rect);
+            self.apply_alpha(1.0);
+            true
+        };
+
+        if shown {
+            window::bring_to_front(self.hwnd);
+            self.fade_start = Some(Instant::now());
+        }
+    }
+
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    pub fn set_color(&mut self, color: &BorderColor) {
+        self.color = D2D1_COLOR_F { r: color.r, g: color.g, b: color.b, a: 1.0 };
     }
 
-    pub fn set_opacity(&self, opacity: f32) {
-        window::set_alpha(self.hwnd, (opacity * 255.0) as u8);
+    pub fn set_shape(&mut self, shape: FlashShape) {
+        self.shape = shape;
     }
 
-    pub fn hide(&self) {
+    /// Set how long the flash shows and how much of that is spent fading out.
+    pub fn set_duration(&mut self, duration_ms: u32, fade_ms: u32) {
+        self.duration = Duration::from_millis(duration_ms as u64);
+        self.fade = Duration::from_millis(fade_ms as u64);
+    }
+
+    /// Switch between per-pixel alpha and the color-key fallback.
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        self.hide();
+        self.surface = surface::attach(self.hwnd, &self.factory, mode == RenderMode::PerPixel);
+    }
+
+    /// Whether a fade is running and needs `tick` calls.
+    pub fn is_animating(&self) -> bool {
+        self.fade_start.is_some()
+    }
+
+    /// Advance the fade by one frame. Returns false once the flash is gone.
+    pub fn tick(&mut self) -> bool {
+        let Some(start) = self.fade_start else { return false };
+        let elapsed = Instant::now().saturating_duration_since(start);
+        if elapsed >= self.duration {
+            self.hide();
+            return false;
+        }
+        self.apply_alpha(animation::hold_then_fade_alpha(elapsed, self.duration, self.fade));
+        true
+    }
+
+    pub fn hide(&mut self) {
+        self.fade_start = None;
         window::hide_overlay(self.hwnd);
     }
 
+    fn alpha_byte(&self, fade: f32) -> u8 {
+        (self.opacity * self.strength * fade * 255.0).round().clamp(0.0, 255.0) as u8
+    }
+
+    /// Apply the configured opacity scaled by `fade` (1.0 → 0.0).
+    fn apply_alpha(&self, fade: f32) {
+        let alpha = self.alpha_byte(fade);
+        if self.surface.is_some() {
+            window::set_layered_alpha(self.hwnd, alpha);
+        } else if self.shape == FlashShape::Fill {
+            window::set_alpha(self.hwnd, alpha);
+        } else {
+            window::set_colorkey_alpha(self.hwnd, alpha);
+        }
+    }
+
     fn render(&self, rect: &RECT) {
         unsafe {
             let w = (rect.right - rect.left).max(1) as u32;
@@ -78,16 +207,156 @@ impl FlashOverlay {
 
             rt.BeginDraw();
 
-            // Render opaque blue — window-level LWA_ALPHA controls transparency
-            let color = D2D1_COLOR_F {
-                r: 0.0,
-                g: 0.47,
-                b: 0.84,
-                a: 1.0,
-            };
-            rt.Clear(Some(&color));
+            // Magenta is keyed out for the partial shapes
+            let clear = D2D1_COLOR_F { r: 1.0, g: 0.0, b: 1.0, a: 1.0 };
+            rt.Clear(Some(&clear));
+
+            self.draw(&rt, w as f32, h as f32, false);
 
             let _ = rt.EndDraw(None, None);
         }
     }
+
+    /// Draw the flash in its full color — window-level alpha controls
+    /// transparency and the fade. When flashing monitor and window together
+    /// the monitor part is drawn weaker so the window stands out.
+    unsafe fn draw(&self, rt: &ID2D1RenderTarget, w: f32, h: f32, per_pixel: bool) {
+        let full = D2D_RECT_F { left: 0.0, top: 0.0, right: w, bottom: h };
+        match self.window_area {
+            None => self.draw_shape(rt, &full, 1.0, per_pixel),
+            Some(window_area) => {
+                // The color key can't blend, so both parts stay solid there
+                let backdrop = if per_pixel { BACKDROP_STRENGTH } else { 1.0 };
+                self.draw_shape(rt, &full, backdrop, per_pixel);
+                self.draw_shape(rt, &window_area, 1.0, per_pixel);
+                if !per_pixel && self.shape == FlashShape::Fill {
+                    // Both fills are the same solid color here
+                    self.draw_window_outline(rt, &window_area);
+                }
+            }
+        }
+    }
+
+    /// Outline `area` in black or white, whichever stands out more from the
+    /// flash color.
+    unsafe fn draw_window_outline(&self, rt: &ID2D1RenderTarget, area: &D2D_RECT_F) {
+        let flash = BorderColor::new(self.color.r, self.color.g, self.color.b, 1.0);
+        let outline = if contrast::contrast_ratio(flash, accent::BLACK)
+            >= contrast::contrast_ratio(flash, accent::WHITE)
+        {
+            accent::BLACK
+        } else {
+            accent::WHITE
+        };
+        let color = D2D1_COLOR_F { r: outline.r, g: outline.g, b: outline.b, a: 1.0 };
+        let Ok(brush) = rt.CreateSolidColorBrush(&color, None) else { return };
+        let half = WINDOW_OUTLINE / 2.0;
+        let rect = D2D_RECT_F {
+            left: area.left + half,
+            top: area.top + half,
+            right: area.right - half,
+            bottom: area.bottom - half,
+        };
+        rt.DrawRectangle(&rect, &brush, WINDOW_OUTLINE, None);
+    }
+
+    /// Draw the configured shape over `area` with the color's alpha set to
+    /// `strength`. Without per-pixel alpha the soft shapes become solid bands
+    /// and corner circles.
+    unsafe fn draw_shape(&self, rt: &ID2D1RenderTarget, area: &D2D_RECT_F, strength: f32, per_pixel: bool) {
+        let color = D2D1_COLOR_F { a: strength, ..self.color };
+        let Ok(solid) = rt.CreateSolidColorBrush(&color, None) else { return };
+        let D2D_RECT_F { left, top, right, bottom } = *area;
+        let short_side = (right - left).min(bottom - top);
+
+        match self.shape {
+            FlashShape::Fill => {
+                rt.FillRectangle(area, &solid);
+            }
+            FlashShape::Edges => {
+                let band = short_side * EDGE_FRACTION;
+                // (band rect, gradient start → end pointing inward)
+                let edges = [
+                    (D2D_RECT_F { bottom: top + band, ..*area }, (left, top), (left, top + band)),
+                    (D2D_RECT_F { top: bottom - band, ..*area }, (left, bottom), (left, bottom - band)),
+                    (D2D_RECT_F { right: left + band, ..*area }, (left, top), (left + band, top)),
+                    (D2D_RECT_F { left: right - band, ..*area }, (right, top), (right - band, top)),
+                ];
+                for (rect, start, end) in edges {
+                    if per_pixel {
+                        let Some(brush) = linear_fade(rt, color, start, end) else { return };
+                        rt.FillRectangle(&rect, &brush);
+                    } else {
+                        rt.FillRectangle(&rect, &solid);
+                    }
+                }
+            }
+            FlashShape::Corners => {
+                let radius = short_side * CORNER_FRACTION;
+                // Keep the glow inside the area when it is only part of the overlay
+                rt.PushAxisAlignedClip(area, D2D1_ANTIALIAS_MODE_ALIASED);
+                for center in [(left, top), (right, top), (left, bottom), (right, bottom)] {
+                    let mut ellipse = D2D1_ELLIPSE { radiusX: radius, radiusY: radius, ..Default::default() };
+                    ellipse.point.X = center.0;
+                    ellipse.point.Y = center.1;
+                    if per_pixel {
+                        let Some(brush) = radial_fade(rt, color, center, radius) else { break };
+                        rt.FillEllipse(&ellipse, &brush);
+                    } else {
+                        // Solid fallback is smaller so it reads as a corner accent
+                        ellipse.radiusX = radius / 2.0;
+                        ellipse.radiusY = radius / 2.0;
+                        rt.FillEllipse(&ellipse, &solid);
+                    }
+                }
+                rt.PopAxisAlignedClip();
+            }
+        }
+    }
+}
+
+/// Gradient stops from `color` to fully transparent.
+fn fade_stops(color: D2D1_COLOR_F) -> [D2D1_GRADIENT_STOP; 2] {
+    [
+        D2D1_GRADIENT_STOP { position: 0.0, color },
+        D2D1_GRADIENT_STOP { position: 1.0, color: D2D1_COLOR_F { a: 0.0, ..color } },
+    ]
+}
+
+unsafe fn linear_fade(
+    rt: &ID2D1RenderTarget,
+    color: D2D1_COLOR_F,
+    start: (f32, f32),
+    end: (f32, f32),
+) -> Option<ID2D1Brush> {
+    let stops = rt
+        .CreateGradientStopCollection(&fade_stops(color), D2D1_GAMMA_2_2, D2D1_EXTEND_MODE_CLAMP)
+        .ok()?;
+    let mut props = D2D1_LINEAR_GRADIENT_BRUSH_PROPERTIES::default();
+    props.startPoint.X = start.0;
+    props.startPoint.Y = start.1;
+    props.endPoint.X = end.0;
+    props.endPoint.Y = end.1;
+    let brush = rt.CreateLinearGradientBrush(&props, None, &stops).ok()?;
+    Some(brush.into())
+}
+
+unsafe fn radial_fade(
+    rt: &ID2D1RenderTarget,
+    color: D2D1_COLOR_F,
+    center: (f32, f32),
+    radius: f32,
+) -> Option<ID2D1Brush> {
+    let stops = rt
+        .CreateGradientStopCollection(&fade_stops(color), D2D1_GAMMA_2_2, D2D1_EXTEND_MODE_CLAMP)
+        .ok()?;
+    let mut props = D2D1_RADIAL_GRADIENT_BRUSH_PROPERTIES {
+        radiusX: radius,
+        radiusY: radius,
+        ..Default::default()
+    };
+    props.center.X = center.0;
+    props.center.Y = center.1;
+    let brush = rt.CreateRadialGradientBrush(&props, None, &stops).ok()?;
+    Some(brush.into())
 }
@@ -3,42 +3,101 @@ use windows::Win32::Graphics::Direct2D::Common::{
     D2D_RECT_F, D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_COLOR_F, D2D1_PIXEL_FORMAT,
 };
 use windows::Win32::Graphics::Direct2D::{
-    D2D1CreateFactory, D2D1_ROUNDED_RECT, ID2D1Factory, ID2D1HwndRenderTarget,
+    D2D1CreateFactory, D2D1_ROUNDED_RECT, ID2D1Factory, ID2D1HwndRenderTarget, ID2D1RenderTarget,
     D2D1_DRAW_TEXT_OPTIONS_NONE, D2D1_FACTORY_TYPE_SINGLE_THREADED,
     D2D1_HWND_RENDER_TARGET_PROPERTIES, D2D1_PRESENT_OPTIONS_IMMEDIATELY,
     D2D1_RENDER_TARGET_PROPERTIES, D2D1_RENDER_TARGET_TYPE_DEFAULT,
 };
 use windows::Win32::Graphics::DirectWrite::{
-    DWriteCreateFactory, IDWriteFactory, DWRITE_FACTORY_TYPE_SHARED,
+    DWriteCreateFactory, IDWriteFactory, IDWriteTextFormat, DWRITE_FACTORY_TYPE_SHARED,
     DWRITE_FONT_STRETCH_NORMAL, DWRITE_FONT_STYLE_NORMAL, DWRITE_FONT_WEIGHT_BOLD,
     DWRITE_MEASURING_MODE_NATURAL, DWRITE_PARAGRAPH_ALIGNMENT_CENTER,
-    DWRITE_TEXT_ALIGNMENT_CENTER,
+    DWRITE_TEXT_ALIGNMENT_CENTER, DWRITE_TEXT_METRICS, DWRITE_WORD_WRAPPING_NO_WRAP,
 };
 use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8A8_UNORM;
 
-use crate::config::BorderColor;
+use crate::config::{BadgeCorner, BadgeShape, BadgeStyle, BorderColor, RenderMode};
+use crate::overlay::surface::{self, LayeredSurface};
 use crate::overlay::window;
 
-const BADGE_W: u32 = 40;
-const BADGE_H: u32 = 30;
-const MARGIN: i32 = 8;
 const CORNER_RADIUS: f32 = 6.0;
+/// Space kept on each side of the text when a badge widens to fit it.
+const TEXT_PADDING_X: f32 = 8.0;
+
+/// Values available to badge templates.
+pub struct BadgeFields<'a> {
+    pub number: usize,
+    pub label: &'a str,
+    pub width: i32,
+    pub height: i32,
+    pub windows: usize,
+}
+
+/// Expand `{n}`, `{label}`, `{w}`, `{h}` and `{windows}` in `template`.
+/// Unknown placeholders are kept as written.
+pub fn format_badge(template: &str, fields: &BadgeFields) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let Some(len) = rest[open..].find('}') else { break };
+        let close = open + len;
+        out.push_str(&rest[..open]);
+        match &rest[open + 1..close] {
+            "n" => out.push_str(&fields.number.to_string()),
+            "label" => out.push_str(fields.label),
+            "w" => out.push_str(&fields.width.to_string()),
+            "h" => out.push_str(&fields.height.to_string()),
+            "windows" => out.push_str(&fields.windows.to_string()),
+            _ => out.push_str(&rest[open..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Badge size after applying its shape: circles are square.
+fn badge_size(style: &BadgeStyle) -> (i32, i32) {
+    let (w, h) = (style.width.max(1), style.height.max(1));
+    match style.shape {
+        BadgeShape::Circle => (w.min(h), w.min(h)),
+        BadgeShape::RoundedRect | BadgeShape::Pill => (w, h),
+    }
+}
+
+/// Screen rect of a `width`×`height` badge anchored to `style.corner` of
+/// `monitor`. Offsets are clamped so the badge never leaves the monitor.
+pub(crate) fn badge_rect(monitor: &RECT, style: &BadgeStyle, width: i32, height: i32) -> RECT {
+    let max_x = (monitor.right - monitor.left - width).max(0);
+    let max_y = (monitor.bottom - monitor.top - height).max(0);
+    let dx = style.offset_x.clamp(0, max_x);
+    let dy = style.offset_y.clamp(0, max_y);
+
+    let left = match style.corner {
+        BadgeCorner::TopLeft | BadgeCorner::BottomLeft => monitor.left + dx,
+        BadgeCorner::TopRight | BadgeCorner::BottomRight => monitor.right - dx - width,
+    };
+    let top = match style.corner {
+        BadgeCorner::TopLeft | BadgeCorner::TopRight => monitor.top + dy,
+        BadgeCorner::BottomLeft | BadgeCorner::BottomRight => monitor.bottom - dy - height,
+    };
+    RECT { left, top, right: left + width, bottom: top + height }
+}
+
+/// Corner radius that gives a `width`×`height` badge its shape.
+fn shape_radius(shape: BadgeShape, width: f32, height: f32) -> f32 {
+    match shape {
+        BadgeShape::RoundedRect => CORNER_RADIUS,
+        BadgeShape::Circle | BadgeShape::Pill => width.min(height) / 2.0,
+    }
+}
 
 fn border_color_to_d2d(c: &BorderColor, alpha: f32) -> D2D1_COLOR_F {
     D2D1_COLOR_F { r: c.r, g: c.g, b: c.b, a: alpha }
 }
 
-const INACTIVE_ALPHA: f32 = 0.6;
 const ACTIVE_ALPHA: f32 = 0.8;
 
-fn inactive_color() -> D2D1_COLOR_F {
-    D2D1_COLOR_F {
-        r: 0.35,
-        g: 0.35,
-        b: 0.35,
-        a: INACTIVE_ALPHA,
-    }
-}
 const TEXT_COLOR: D2D1_COLOR_F = D2D1_COLOR_F {
     r: 1.0,
     g: 1.0,
@@ -46,7 +105,7 @@ const TEXT_COLOR: D2D1_COLOR_F = D2D1_COLOR_F {
     a: 1.0,
 };
 
-/// One badge per monitor, displayed at bottom-left corner.
+/// One badge per monitor, anchored to a configurable corner.
 pub struct MonitorIndicators {
     badges: Vec<Badge>,
 }
@@ -54,33 +113,38 @@ pub struct MonitorIndicators {
 struct Badge {
     hwnd: HWND,
     index: usize,
+    monitor_rect: RECT,
+    style: BadgeStyle,
+    text: String,
+    rect: RECT,
     d2d_factory: ID2D1Factory,
     dwrite_factory: IDWriteFactory,
     render_target: Option<ID2D1HwndRenderTarget>,
+    /// Per-pixel alpha surface; None while on the color key.
+    surface: Option<LayeredSurface>,
     is_active: bool,
     active_color: D2D1_COLOR_F,
+    inactive_color: D2D1_COLOR_F,
 }
 
 impl MonitorIndicators {
-    pub fn new(monitor_rects: &[RECT], active_color: &BorderColor) -> Option<Self> {
+    /// Create one badge per monitor; `styles` holds each monitor's badge style
+    /// in the same order as `monitor_rects`.
+    pub fn new(
+        monitor_rects: &[RECT],
+        styles: &[BadgeStyle],
+        active_color: &BorderColor,
+        inactive_color: &BorderColor,
+        render_mode: RenderMode,
+    ) -> Option<Self> {
         let d2d_active = border_color_to_d2d(active_color, ACTIVE_ALPHA);
+        let d2d_inactive = border_color_to_d2d(inactive_color, inactive_color.a);
         let mut badges = Vec::with_capacity(monitor_rects.len());
 
-        for (i, mon_rect) in monitor_rects.iter().enumerate() {
+        for (i, (mon_rect, style)) in monitor_rects.iter().zip(styles).enumerate() {
             let class_name = format!("WhereIsMyWindowIndicator{}", i);
-            let hwnd = window::create_overlay_window(
-                &class_name,
-                BADGE_W as i32,
-                BADGE_H as i32,
-            )?;
-
-            let badge_rect = RECT {
-                left: mon_rect.left + MARGIN,
-                top: mon_rect.bottom - BADGE_H as i32 - MARGIN,
-                right: mon_rect.left + MARGIN + BADGE_W as i32,
-                bottom: mon_rect.bottom - MARGIN,
-            };
-            window::reposition_overlay(hwnd, &badge_rect);
+            let (w, h) = badge_size(style);
+            let hwnd = window::create_overlay_window(&class_name, w, h)?;
 
             let d2d_factory: ID2D1Factory = unsafe {
                 D2D1CreateFactory(D2D1_FACTORY_TYPE_SINGLE_THREADED, None).ok()?
@@ -90,18 +154,25 @@ impl MonitorIndicators {
                 DWriteCreateFactory(DWRITE_FACTORY_TYPE_SHARED).ok()?
             };
 
-            window::set_colorkey(hwnd);
+            let surface =
+                surface::attach(hwnd, &d2d_factory, render_mode == RenderMode::PerPixel);
 
             let mut badge = Badge {
                 hwnd,
                 index: i,
+                monitor_rect: *mon_rect,
+                style: *style,
+                text: (i + 1).to_string(),
+                rect: RECT::default(),
                 d2d_factory,
                 dwrite_factory,
                 render_target: None,
+                surface,
                 is_active: false,
                 active_color: d2d_active,
+                inactive_color: d2d_inactive,
             };
-            badge.ensure_render_target();
+            badge.layout();
             badge.render();
             window::show_overlay(hwnd);
 
@@ -127,10 +198,12 @@ impl MonitorIndicators {
         }
     }
 
-    /// Bring all badge windows to the top of the TOPMOST z-order.
+    /// Bring visible badge windows to the top of the TOPMOST z-order.
     pub fn bring_to_front(&self) {
         for badge in &self.badges {
-            window::bring_to_front(badge.hwnd);
+            if window::is_visible(badge.hwnd) {
+                window::bring_to_front(badge.hwnd);
+            }
         }
     }
 
@@ -151,19 +224,125 @@ impl MonitorIndicators {
         self.bring_to_front();
     }
 
-    /// Update the active badge color (synced with border color).
+    /// Switch all badges between per-pixel alpha and the color-key fallback.
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        for badge in &mut self.badges {
+            badge.render_target = None;
+            badge.surface =
+                surface::attach(badge.hwnd, &badge.d2d_factory, mode == RenderMode::PerPixel);
+            badge.render();
+        }
+    }
+
+    /// Re-place and re-render badges whose style changed.
+    pub fn set_styles(&mut self, styles: &[BadgeStyle]) {
+        for (badge, style) in self.badges.iter_mut().zip(styles) {
+            if badge.style == *style {
+                continue;
+            }
+            badge.style = *style;
+            badge.layout();
+            badge.render();
+        }
+    }
+
+    /// Set each badge's text (already expanded from its template), widening
+    /// badges whose text no longer fits.
+    pub fn set_texts(&mut self, texts: &[String]) {
+        for (badge, text) in self.badges.iter_mut().zip(texts) {
+            if badge.text == *text {
+                continue;
+            }
+            badge.text = text.clone();
+            badge.layout();
+            badge.render();
+        }
+    }
+
+    /// Update the active badge color (the border color unless the theme or
+    /// the focused monitor sets one).
     pub fn set_active_color(&mut self, color: &BorderColor) {
         let d2d_color = border_color_to_d2d(color, ACTIVE_ALPHA);
         for badge in &mut self.badges {
+            if badge.active_color == d2d_color {
+                continue;
+            }
             badge.active_color = d2d_color;
             if badge.is_active {
                 badge.render();
             }
         }
     }
+
+    /// Update the color of the badges on unfocused monitors, alpha included.
+    pub fn set_inactive_color(&mut self, color: &BorderColor) {
+        let d2d_color = border_color_to_d2d(color, color.a);
+        for badge in &mut self.badges {
+            badge.inactive_color = d2d_color;
+            if !badge.is_active {
+                badge.render();
+            }
+        }
+    }
 }
 
 impl Badge {
+    /// Size the badge for its style and text, and move it into place.
+    fn layout(&mut self) {
+        let (min_w, h) = badge_size(&self.style);
+        let text_w = (self.measure_text() + 2.0 * TEXT_PADDING_X).ceil() as i32;
+        let rect = badge_rect(&self.monitor_rect, &self.style, min_w.max(text_w), h);
+        if rect == self.rect {
+            return;
+        }
+        let resized = rect.right - rect.left != self.rect.right - self.rect.left
+            || rect.bottom - rect.top != self.rect.bottom - self.rect.top;
+        if resized {
+            // Hwnd render targets have a fixed pixel size
+            self.render_target = None;
+        }
+        self.rect = rect;
+        window::reposition_overlay(self.hwnd, &rect);
+    }
+
+    fn text_format(&self) -> Option<IDWriteTextFormat> {
+        unsafe {
+            let format = self
+                .dwrite_factory
+                .CreateTextFormat(
+                    windows::core::w!("Segoe UI"),
+                    None,
+                    DWRITE_FONT_WEIGHT_BOLD,
+                    DWRITE_FONT_STYLE_NORMAL,
+                    DWRITE_FONT_STRETCH_NORMAL,
+                    self.style.font_size,
+                    windows::core::w!(""),
+                )
+                .ok()?;
+            let _ = format.SetTextAlignment(DWRITE_TEXT_ALIGNMENT_CENTER);
+            let _ = format.SetParagraphAlignment(DWRITE_PARAGRAPH_ALIGNMENT_CENTER);
+            let _ = format.SetWordWrapping(DWRITE_WORD_WRAPPING_NO_WRAP);
+            Some(format)
+        }
+    }
+
+    /// Natural width of the badge text in px.
+    fn measure_text(&self) -> f32 {
+        let Some(format) = self.text_format() else { return 0.0 };
+        let wide: Vec<u16> = self.text.encode_utf16().collect();
+        unsafe {
+            let Ok(layout) = self.dwrite_factory.CreateTextLayout(&wide, &format, f32::MAX, f32::MAX)
+            else {
+                return 0.0;
+            };
+            let mut metrics = DWRITE_TEXT_METRICS::default();
+            if layout.GetMetrics(&mut metrics).is_err() {
+                return 0.0;
+            }
+            metrics.widthIncludingTrailingWhitespace
+        }
+    }
+
     fn ensure_render_target(&mut self) {
         if self.render_target.is_some() {
             return;
@@ -181,8 +360,8 @@ impl Badge {
             let hwnd_props = D2D1_HWND_RENDER_TARGET_PROPERTIES {
                 hwnd: self.hwnd,
                 pixelSize: windows::Win32::Graphics::Direct2D::Common::D2D_SIZE_U {
-                    width: BADGE_W,
-                    height: BADGE_H,
+                    width: (self.rect.right - self.rect.left) as u32,
+                    height: (self.rect.bottom - self.rect.top) as u32,
                 },
                 presentOptions: D2D1_PRESENT_OPTIONS_IMMEDIATELY,
             };
@@ -197,37 +376,22 @@ impl Badge {
     }
 
     fn render(&mut self) {
+        let (w, h) = (self.rect.right - self.rect.left, self.rect.bottom - self.rect.top);
+        if let Some(mut layered) = self.surface.take() {
+            let drawn = layered.draw(w, h, |rt| unsafe {
+                self.draw(rt, w as f32, h as f32);
+            });
+            if drawn {
+                layered.present(self.hwnd, &self.rect, 255);
+            }
+            self.surface = Some(layered);
+            return;
+        }
+
         self.ensure_render_target();
         let Some(rt) = &self.render_target else { return };
 
         unsafe {
-            let bg_color = if self.is_active {
-                self.active_color
-            } else {
-                inactive_color()
-            };
-            let Ok(bg_brush) = rt.CreateSolidColorBrush(&bg_color, None) else {
-                return;
-            };
-            let Ok(text_brush) = rt.CreateSolidColorBrush(&TEXT_COLOR, None) else {
-                return;
-            };
-
-            let Ok(text_format) = self.dwrite_factory.CreateTextFormat(
-                windows::core::w!("Segoe UI"),
-                None,
-                DWRITE_FONT_WEIGHT_BOLD,
-                DWRITE_FONT_STYLE_NORMAL,
-                DWRITE_FONT_STRETCH_NORMAL,
-                15.0,
-                windows::core::w!(""),
-            ) else {
-                return;
-            };
-
-            let _ = text_format.SetTextAlignment(DWRITE_TEXT_ALIGNMENT_CENTER);
-            let _ = text_format.SetParagraphAlignment(DWRITE_PARAGRAPH_ALIGNMENT_CENTER);
-
             rt.BeginDraw();
 
             let clear = D2D1_COLOR_F {
@@ -238,36 +402,128 @@ impl Badge {
             };
             rt.Clear(Some(&clear));
 
-            let rounded_rect = D2D1_ROUNDED_RECT {
-                rect: D2D_RECT_F {
-                    left: 0.0,
-                    top: 0.0,
-                    right: BADGE_W as f32,
-                    bottom: BADGE_H as f32,
-                },
-                radiusX: CORNER_RADIUS,
-                radiusY: CORNER_RADIUS,
-            };
-            rt.FillRoundedRectangle(&rounded_rect, &bg_brush);
+            self.draw(rt, w as f32, h as f32);
+
+            let _ = rt.EndDraw(None, None);
+        }
+    }
 
-            let label = format!("{}", self.index + 1);
-            let label_wide: Vec<u16> = label.encode_utf16().collect();
-            let layout_rect = D2D_RECT_F {
+    /// Draw the badge background and text onto a cleared target.
+    unsafe fn draw(&self, rt: &ID2D1RenderTarget, w: f32, h: f32) {
+        let bg_color = if self.is_active {
+            self.active_color
+        } else {
+            self.inactive_color
+        };
+        let Ok(bg_brush) = rt.CreateSolidColorBrush(&bg_color, None) else {
+            return;
+        };
+        let Ok(text_brush) = rt.CreateSolidColorBrush(&TEXT_COLOR, None) else {
+            return;
+        };
+
+        let Some(text_format) = self.text_format() else {
+            return;
+        };
+
+        let radius = shape_radius(self.style.shape, w, h);
+        let rounded_rect = D2D1_ROUNDED_RECT {
+            rect: D2D_RECT_F {
                 left: 0.0,
                 top: 0.0,
-                right: BADGE_W as f32,
-                bottom: BADGE_H as f32,
-            };
-            rt.DrawText(
-                &label_wide,
-                &text_format,
-                &layout_rect,
-                &text_brush,
-                D2D1_DRAW_TEXT_OPTIONS_NONE,
-                DWRITE_MEASURING_MODE_NATURAL,
-            );
+                right: w,
+                bottom: h,
+            },
+            radiusX: radius,
+            radiusY: radius,
+        };
+        rt.FillRoundedRectangle(&rounded_rect, &bg_brush);
+
+        let label_wide: Vec<u16> = self.text.encode_utf16().collect();
+        let layout_rect = D2D_RECT_F {
+            left: 0.0,
+            top: 0.0,
+            right: w,
+            bottom: h,
+        };
+        rt.DrawText(
+            &label_wide,
+            &text_format,
+            &layout_rect,
+            &text_brush,
+            D2D1_DRAW_TEXT_OPTIONS_NONE,
+            DWRITE_MEASURING_MODE_NATURAL,
+        );
+    }
+}
 
-            let _ = rt.EndDraw(None, None);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> RECT {
+        RECT { left, top, right, bottom }
+    }
+
+    fn style(corner: BadgeCorner, shape: BadgeShape) -> BadgeStyle {
+        BadgeStyle {
+            corner,
+            offset_x: 8,
+            offset_y: 8,
+            width: 40,
+            height: 30,
+            shape,
+            font_size: 15.0,
         }
     }
+
+    #[test]
+    fn default_matches_bottom_left_margin() {
+        let monitor = rect(0, 0, 1920, 1080);
+        let s = style(BadgeCorner::BottomLeft, BadgeShape::RoundedRect);
+        assert_eq!(badge_rect(&monitor, &s, 40, 30), rect(8, 1042, 48, 1072));
+    }
+
+    #[test]
+    fn anchors_to_each_corner() {
+        let monitor = rect(-1920, 100, 0, 1180);
+        let cases = [
+            (BadgeCorner::TopLeft, rect(-1912, 108, -1872, 138)),
+            (BadgeCorner::TopRight, rect(-48, 108, -8, 138)),
+            (BadgeCorner::BottomLeft, rect(-1912, 1142, -1872, 1172)),
+            (BadgeCorner::BottomRight, rect(-48, 1142, -8, 1172)),
+        ];
+        for (corner, expected) in cases {
+            let s = style(corner, BadgeShape::RoundedRect);
+            assert_eq!(badge_rect(&monitor, &s, 40, 30), expected, "{:?}", corner);
+        }
+    }
+
+    #[test]
+    fn offset_is_clamped_to_monitor() {
+        let monitor = rect(0, 0, 100, 60);
+        let mut s = style(BadgeCorner::BottomRight, BadgeShape::RoundedRect);
+        s.offset_x = 500;
+        s.offset_y = -20;
+        assert_eq!(badge_rect(&monitor, &s, 40, 30), rect(0, 30, 40, 60));
+    }
+
+    #[test]
+    fn template_expands_placeholders() {
+        let fields = BadgeFields { number: 2, label: "Left", width: 2560, height: 1440, windows: 5 };
+        assert_eq!(format_badge("{n}", &fields), "2");
+        assert_eq!(format_badge("{label} {w}x{h}", &fields), "Left 2560x1440");
+        assert_eq!(format_badge("{n}·{windows}", &fields), "2·5");
+        assert_eq!(format_badge("{x} {n", &fields), "{x} {n");
+    }
+
+    #[test]
+    fn circle_uses_smaller_side() {
+        let s = style(BadgeCorner::TopLeft, BadgeShape::Circle);
+        assert_eq!(badge_size(&s), (30, 30));
+        let s = style(BadgeCorner::TopLeft, BadgeShape::Pill);
+        assert_eq!(badge_size(&s), (40, 30));
+        assert_eq!(shape_radius(BadgeShape::Pill, 40.0, 30.0), 15.0);
+        assert_eq!(shape_radius(BadgeShape::RoundedRect, 40.0, 30.0), CORNER_RADIUS);
+    }
 }
@@ -0,0 +1,249 @@
+use std::time::{Duration, Instant};
+
+use windows::Win32::Foundation::{HWND, RECT};
+use windows::Win32::Graphics::Direct2D::Common::{
+    D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_COLOR_F, D2D1_PIXEL_FORMAT, D2D_SIZE_U,
+};
+use windows::Win32::Graphics::Direct2D::{
+    D2D1CreateFactory, ID2D1Factory, ID2D1HwndRenderTarget, ID2D1RenderTarget,
+    D2D1_ANTIALIAS_MODE_ALIASED, D2D1_ELLIPSE, D2D1_FACTORY_TYPE_SINGLE_THREADED,
+    D2D1_HWND_RENDER_TARGET_PROPERTIES, D2D1_PRESENT_OPTIONS_IMMEDIATELY,
+    D2D1_RENDER_TARGET_PROPERTIES, D2D1_RENDER_TARGET_TYPE_DEFAULT,
+};
+use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8A8_UNORM;
+
+use crate::config::{BorderColor, Easing, RenderMode};
+use crate::overlay::animation;
+use crate::overlay::surface::{self, LayeredSurface};
+use crate::overlay::window;
+
+/// Radius (px) each ring grows to.
+const MAX_RADIUS: i32 = 120;
+const RING_WIDTH: f32 = 4.0;
+const RING_COUNT: usize = 3;
+/// How long one ring takes to expand and fade.
+const RING_DURATION: Duration = Duration::from_millis(600);
+/// Delay between the start of consecutive rings.
+const RING_STAGGER: Duration = Duration::from_millis(150);
+
+/// Radius (as a fraction of MAX_RADIUS) and opacity of ring `index` at
+/// `elapsed` into the ripple, or None if it hasn't started or has faded.
+fn ring_state(elapsed: Duration, index: usize) -> Option<(f32, f32)> {
+    let elapsed = elapsed.checked_sub(RING_STAGGER * index as u32)?;
+    if elapsed >= RING_DURATION {
+        return None;
+    }
+    let t = elapsed.as_secs_f32() / RING_DURATION.as_secs_f32();
+    Some((animation::ease(Easing::EaseOut, t), 1.0 - t))
+}
+
+/// Total length of the ripple, last ring included.
+fn ripple_duration() -> Duration {
+    RING_DURATION + RING_STAGGER * (RING_COUNT as u32 - 1)
+}
+
+/// Expanding rings around the cursor, clipped to the cursor's monitor.
+/// Redrawn every frame while `tick` is driven by the animation timer.
+pub struct CursorLocator {
+    pub hwnd: HWND,
+    factory: ID2D1Factory,
+    /// Per-pixel alpha surface; None while on the color key.
+    surface: Option<LayeredSurface>,
+    /// Cached color-key target, dropped when the window size changes.
+    render_target: Option<ID2D1HwndRenderTarget>,
+    color: D2D1_COLOR_F,
+    /// Window rect: a square around the cursor cut to its monitor.
+    rect: RECT,
+    /// Cursor position relative to `rect`.
+    center: (f32, f32),
+    start: Option<Instant>,
+}
+
+impl CursorLocator {
+    pub fn new(color: &BorderColor, render_mode: RenderMode) -> Option<Self> {
+        let hwnd = window::create_overlay_window("WhereIsMyWindowLocator", 1, 1)?;
+        let factory: ID2D1Factory = unsafe {
+            D2D1CreateFactory(D2D1_FACTORY_TYPE_SINGLE_THREADED, None).ok()?
+        };
+
+        let surface = surface::attach(hwnd, &factory, render_mode == RenderMode::PerPixel);
+
+        Some(Self {
+            hwnd,
+            factory,
+            surface,
+            render_target: None,
+            color: D2D1_COLOR_F { r: color.r, g: color.g, b: color.b, a: 1.0 },
+            rect: RECT::default(),
+            center: (0.0, 0.0),
+            start: None,
+        })
+    }
+
+    /// Start a ripple at screen point (`x`, `y`) on the monitor `monitor_rect`.
+    pub fn locate(&mut self, x: i32, y: i32, monitor_rect: &RECT) {
+        let rect = RECT {
+            left: (x - MAX_RADIUS).max(monitor_rect.left),
+            top: (y - MAX_RADIUS).max(monitor_rect.top),
+            right: (x + MAX_RADIUS).min(monitor_rect.right),
+            bottom: (y + MAX_RADIUS).min(monitor_rect.bottom),
+        };
+        let resized = rect.right - rect.left != self.rect.right - self.rect.left
+            || rect.bottom - rect.top != self.rect.bottom - self.rect.top;
+        if resized {
+            self.render_target = None;
+        }
+        self.rect = rect;
+        self.center = ((x - rect.left) as f32, (y - rect.top) as f32);
+        if self.surface.is_none() {
+            window::reposition_overlay(self.hwnd, &rect);
+        }
+
+        self.start = Some(Instant::now());
+        if self.render(Duration::ZERO) {
+            window::bring_to_front(self.hwnd);
+        }
+    }
+
+    pub fn set_color(&mut self, color: &BorderColor) {
+        self.color = D2D1_COLOR_F { r: color.r, g: color.g, b: color.b, a: 1.0 };
+    }
+
+    /// Switch between per-pixel alpha and the color-key fallback.
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        self.hide();
+        self.render_target = None;
+        self.surface = surface::attach(self.hwnd, &self.factory, mode == RenderMode::PerPixel);
+    }
+
+    /// Whether a ripple is running and needs `tick` calls.
+    pub fn is_animating(&self) -> bool {
+        self.start.is_some()
+    }
+
+    /// Draw the next frame. Returns false once the ripple is over.
+    pub fn tick(&mut self) -> bool {
+        let Some(start) = self.start else { return false };
+        let elapsed = Instant::now().saturating_duration_since(start);
+        if elapsed >= ripple_duration() {
+            self.hide();
+            return false;
+        }
+        self.render(elapsed);
+        true
+    }
+
+    pub fn hide(&mut self) {
+        self.start = None;
+        window::hide_overlay(self.hwnd);
+    }
+
+    fn render(&mut self, elapsed: Duration) -> bool {
+        let w = self.rect.right - self.rect.left;
+        let h = self.rect.bottom - self.rect.top;
+
+        if let Some(mut layered) = self.surface.take() {
+            let drawn = layered.draw(w, h, |rt| unsafe { self.draw(rt, elapsed, true) });
+            let presented = drawn && layered.present(self.hwnd, &self.rect, 255);
+            self.surface = Some(layered);
+            return presented;
+        }
+
+        if self.render_target.is_none() {
+            self.render_target = self.create_render_target(w, h);
+        }
+        let Some(rt) = &self.render_target else { return false };
+
+        unsafe {
+            rt.BeginDraw();
+
+            let clear = D2D1_COLOR_F { r: 1.0, g: 0.0, b: 1.0, a: 1.0 };
+            rt.Clear(Some(&clear));
+
+            // Anti-aliased edges would blend with the magenta color key
+            rt.SetAntialiasMode(D2D1_ANTIALIAS_MODE_ALIASED);
+
+            self.draw(rt, elapsed, false);
+
+            if rt.EndDraw(None, None).is_err() {
+                return false;
+            }
+        }
+        // The color key can't fade rings one by one; fade them together
+        let fade = animation::fade_alpha(elapsed, ripple_duration());
+        window::set_colorkey_alpha(self.hwnd, (fade * 255.0).round() as u8);
+        true
+    }
+
+    fn create_render_target(&self, width: i32, height: i32) -> Option<ID2D1HwndRenderTarget> {
+        unsafe {
+            let render_props = D2D1_RENDER_TARGET_PROPERTIES {
+                r#type: D2D1_RENDER_TARGET_TYPE_DEFAULT,
+                pixelFormat: D2D1_PIXEL_FORMAT {
+                    format: DXGI_FORMAT_B8G8R8A8_UNORM,
+                    alphaMode: D2D1_ALPHA_MODE_PREMULTIPLIED,
+                },
+                ..Default::default()
+            };
+
+            let hwnd_props = D2D1_HWND_RENDER_TARGET_PROPERTIES {
+                hwnd: self.hwnd,
+                pixelSize: D2D_SIZE_U {
+                    width: width.max(1) as u32,
+                    height: height.max(1) as u32,
+                },
+                presentOptions: D2D1_PRESENT_OPTIONS_IMMEDIATELY,
+            };
+
+            self.factory.CreateHwndRenderTarget(&render_props, &hwnd_props).ok()
+        }
+    }
+
+    /// Draw every live ring. With per-pixel alpha each ring fades on its own.
+    unsafe fn draw(&self, rt: &ID2D1RenderTarget, elapsed: Duration, per_pixel: bool) {
+        let max = MAX_RADIUS as f32 - RING_WIDTH;
+        for index in 0..RING_COUNT {
+            let Some((grow, opacity)) = ring_state(elapsed, index) else { continue };
+            let alpha = if per_pixel { opacity } else { 1.0 };
+            let color = D2D1_COLOR_F { a: alpha, ..self.color };
+            let Ok(brush) = rt.CreateSolidColorBrush(&color, None) else { continue };
+
+            let radius = (max * grow).max(1.0);
+            let mut ellipse = D2D1_ELLIPSE { radiusX: radius, radiusY: radius, ..Default::default() };
+            ellipse.point.X = self.center.0;
+            ellipse.point.Y = self.center.1;
+            rt.DrawEllipse(&ellipse, &brush, RING_WIDTH, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rings_start_staggered() {
+        assert!(ring_state(Duration::ZERO, 0).is_some());
+        assert!(ring_state(Duration::ZERO, 1).is_none());
+        assert!(ring_state(RING_STAGGER, 1).is_some());
+        assert!(ring_state(RING_STAGGER, 2).is_none());
+    }
+
+    #[test]
+    fn rings_grow_and_fade() {
+        let (early_radius, early_alpha) = ring_state(Duration::from_millis(100), 0).unwrap();
+        let (late_radius, late_alpha) = ring_state(Duration::from_millis(500), 0).unwrap();
+        assert!(late_radius > early_radius);
+        assert!(late_alpha < early_alpha);
+        assert_eq!(ring_state(Duration::ZERO, 0), Some((0.0, 1.0)));
+    }
+
+    #[test]
+    fn ripple_ends_with_last_ring() {
+        let end = ripple_duration();
+        assert!(ring_state(end - Duration::from_millis(1), RING_COUNT - 1).is_some());
+        for index in 0..RING_COUNT {
+            assert!(ring_state(end, index).is_none());
+        }
+    }
+}
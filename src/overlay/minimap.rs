@@ -0,0 +1,410 @@
+use windows::Win32::Foundation::{HWND, RECT};
+use windows::Win32::Graphics::Direct2D::Common::{
+    D2D_RECT_F, D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_COLOR_F, D2D1_PIXEL_FORMAT,
+};
+use windows::Win32::Graphics::Direct2D::{
+    D2D1CreateFactory, ID2D1Factory, ID2D1HwndRenderTarget, ID2D1RenderTarget, D2D1_ELLIPSE,
+    D2D1_FACTORY_TYPE_SINGLE_THREADED, D2D1_HWND_RENDER_TARGET_PROPERTIES,
+    D2D1_PRESENT_OPTIONS_IMMEDIATELY, D2D1_RENDER_TARGET_PROPERTIES,
+    D2D1_RENDER_TARGET_TYPE_DEFAULT, D2D1_ROUNDED_RECT,
+};
+use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8A8_UNORM;
+
+use crate::config::{BadgeStyle, BorderColor, MiniMapScope, RenderMode};
+use crate::overlay::indicator;
+use crate::overlay::surface::{self, LayeredSurface};
+use crate::overlay::window;
+
+/// Space between the map background edge and the monitor rects.
+const PADDING: f32 = 6.0;
+const CORNER_RADIUS: f32 = 6.0;
+const DOT_RADIUS: f32 = 3.0;
+/// Gap left between adjacent monitors so they read as separate screens.
+const MONITOR_GAP: f32 = 1.0;
+
+const BACKGROUND: D2D1_COLOR_F = D2D1_COLOR_F { r: 0.1, g: 0.1, b: 0.1, a: 0.6 };
+const INACTIVE: D2D1_COLOR_F = D2D1_COLOR_F { r: 0.45, g: 0.45, b: 0.45, a: 1.0 };
+const DOT: D2D1_COLOR_F = D2D1_COLOR_F { r: 1.0, g: 1.0, b: 1.0, a: 1.0 };
+const DOT_OUTLINE: D2D1_COLOR_F = D2D1_COLOR_F { r: 0.0, g: 0.0, b: 0.0, a: 1.0 };
+
+/// Bounding rect of all monitors.
+fn layout_bounds(monitor_rects: &[RECT]) -> RECT {
+    monitor_rects
+        .iter()
+        .copied()
+        .reduce(|a, b| RECT {
+            left: a.left.min(b.left),
+            top: a.top.min(b.top),
+            right: a.right.max(b.right),
+            bottom: a.bottom.max(b.bottom),
+        })
+        .unwrap_or_default()
+}
+
+/// Scale from screen px to map px that fits `bounds` into a `width`-wide map.
+fn map_scale(bounds: &RECT, width: i32) -> f32 {
+    let span = (bounds.right - bounds.left).max(1) as f32;
+    ((width as f32 - 2.0 * PADDING) / span).max(0.0)
+}
+
+/// Map height that keeps the layout's aspect ratio at `width`.
+fn map_height(bounds: &RECT, width: i32) -> i32 {
+    let span = (bounds.bottom - bounds.top).max(0) as f32;
+    (span * map_scale(bounds, width) + 2.0 * PADDING).ceil() as i32
+}
+
+/// Screen point → map point.
+fn to_map(x: i32, y: i32, bounds: &RECT, scale: f32) -> (f32, f32) {
+    (
+        PADDING + (x - bounds.left) as f32 * scale,
+        PADDING + (y - bounds.top) as f32 * scale,
+    )
+}
+
+/// Map position of the dot for a window: its center, in whole map px.
+fn dot_position(window_rect: &RECT, bounds: &RECT, scale: f32) -> (i32, i32) {
+    let center_x = window_rect.left + (window_rect.right - window_rect.left) / 2;
+    let center_y = window_rect.top + (window_rect.bottom - window_rect.top) / 2;
+    let (x, y) = to_map(center_x, center_y, bounds, scale);
+    (x.round() as i32, y.round() as i32)
+}
+
+/// Small scaled map of the whole monitor layout, with the active monitor
+/// highlighted and a dot where the focused window is. One map window per
+/// monitor, anchored where that monitor's badge would be.
+pub struct MiniMap {
+    maps: Vec<MapWindow>,
+    d2d_factory: ID2D1Factory,
+    monitor_rects: Vec<RECT>,
+    bounds: RECT,
+    width: i32,
+    scope: MiniMapScope,
+    active: usize,
+    /// Focused window center in whole map px, so sub-pixel moves don't re-render.
+    dot: Option<(i32, i32)>,
+    active_color: D2D1_COLOR_F,
+    visible: bool,
+}
+
+struct MapWindow {
+    hwnd: HWND,
+    monitor_rect: RECT,
+    style: BadgeStyle,
+    rect: RECT,
+    render_target: Option<ID2D1HwndRenderTarget>,
+    /// Per-pixel alpha surface; None while on the color key.
+    surface: Option<LayeredSurface>,
+}
+
+impl MiniMap {
+    /// Create one map window per monitor; `styles` supplies each monitor's
+    /// badge corner and offset, in `monitor_rects` order.
+    pub fn new(
+        monitor_rects: &[RECT],
+        styles: &[BadgeStyle],
+        width: i32,
+        active_color: &BorderColor,
+        render_mode: RenderMode,
+        scope: MiniMapScope,
+    ) -> Option<Self> {
+        let d2d_factory: ID2D1Factory = unsafe {
+            D2D1CreateFactory(D2D1_FACTORY_TYPE_SINGLE_THREADED, None).ok()?
+        };
+
+        let mut maps = Vec::with_capacity(monitor_rects.len());
+        for (i, (mon_rect, style)) in monitor_rects.iter().zip(styles).enumerate() {
+            let class_name = format!("WhereIsMyWindowMiniMap{}", i);
+            let hwnd = window::create_overlay_window(&class_name, 1, 1)?;
+            let surface =
+                surface::attach(hwnd, &d2d_factory, render_mode == RenderMode::PerPixel);
+            maps.push(MapWindow {
+                hwnd,
+                monitor_rect: *mon_rect,
+                style: *style,
+                rect: RECT::default(),
+                render_target: None,
+                surface,
+            });
+        }
+
+        let mut minimap = Self {
+            maps,
+            d2d_factory,
+            monitor_rects: monitor_rects.to_vec(),
+            bounds: layout_bounds(monitor_rects),
+            width,
+            scope,
+            active: 0,
+            dot: None,
+            active_color: D2D1_COLOR_F { r: active_color.r, g: active_color.g, b: active_color.b, a: 1.0 },
+            visible: false,
+        };
+        minimap.layout();
+        Some(minimap)
+    }
+
+    /// Update the active monitor and the focused window's position.
+    pub fn set_focus(&mut self, active: usize, window_rect: Option<&RECT>) {
+        let scale = map_scale(&self.bounds, self.width);
+        let dot = window_rect.map(|r| dot_position(r, &self.bounds, scale));
+        if active == self.active && dot == self.dot {
+            return;
+        }
+        let active_changed = active != self.active;
+        self.active = active;
+        self.dot = dot;
+        self.render_all();
+        if active_changed && self.visible {
+            self.show();
+        }
+    }
+
+    /// Re-place the maps for new badge styles or map width.
+    pub fn set_layout(&mut self, styles: &[BadgeStyle], width: i32) {
+        let changed = width != self.width
+            || self.maps.iter().zip(styles).any(|(map, style)| map.style != *style);
+        if !changed {
+            return;
+        }
+        self.width = width;
+        for (map, style) in self.maps.iter_mut().zip(styles) {
+            map.style = *style;
+        }
+        self.dot = None;
+        self.layout();
+    }
+
+    pub fn set_scope(&mut self, scope: MiniMapScope) {
+        if scope != self.scope {
+            self.scope = scope;
+            if self.visible {
+                self.show();
+            }
+        }
+    }
+
+    pub fn set_active_color(&mut self, color: &BorderColor) {
+        self.active_color = D2D1_COLOR_F { r: color.r, g: color.g, b: color.b, a: 1.0 };
+        self.render_all();
+    }
+
+    /// Switch all maps between per-pixel alpha and the color-key fallback.
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        for map in &mut self.maps {
+            map.render_target = None;
+            map.surface =
+                surface::attach(map.hwnd, &self.d2d_factory, mode == RenderMode::PerPixel);
+        }
+        self.render_all();
+    }
+
+    /// Show the maps the scope calls for: all of them, or the active monitor's.
+    pub fn show(&mut self) {
+        self.visible = true;
+        for (i, map) in self.maps.iter().enumerate() {
+            if self.scope == MiniMapScope::EveryMonitor || i == self.active {
+                window::bring_to_front(map.hwnd);
+            } else {
+                window::hide_overlay(map.hwnd);
+            }
+        }
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+        for map in &self.maps {
+            window::hide_overlay(map.hwnd);
+        }
+    }
+
+    pub fn hwnd_list(&self) -> Vec<isize> {
+        self.maps.iter().map(|m| m.hwnd.0 as isize).collect()
+    }
+
+    fn layout(&mut self) {
+        let height = map_height(&self.bounds, self.width);
+        for map in &mut self.maps {
+            let rect = indicator::badge_rect(&map.monitor_rect, &map.style, self.width, height);
+            if rect != map.rect {
+                map.rect = rect;
+                // Hwnd render targets have a fixed pixel size
+                map.render_target = None;
+                window::reposition_overlay(map.hwnd, &rect);
+            }
+        }
+        self.render_all();
+    }
+
+    fn render_all(&mut self) {
+        for i in 0..self.maps.len() {
+            self.render(i);
+        }
+    }
+
+    fn render(&mut self, index: usize) {
+        let rect = self.maps[index].rect;
+        let (w, h) = (rect.right - rect.left, rect.bottom - rect.top);
+
+        if let Some(mut layered) = self.maps[index].surface.take() {
+            let drawn = layered.draw(w, h, |rt| unsafe { self.draw(rt, w as f32, h as f32, true) });
+            if drawn {
+                layered.present(self.maps[index].hwnd, &rect, 255);
+            }
+            self.maps[index].surface = Some(layered);
+            return;
+        }
+
+        if self.maps[index].render_target.is_none() {
+            self.maps[index].render_target = self.create_render_target(self.maps[index].hwnd, w, h);
+        }
+        let Some(rt) = &self.maps[index].render_target else { return };
+
+        unsafe {
+            rt.BeginDraw();
+
+            let clear = D2D1_COLOR_F {
+                r: 1.0,
+                g: 0.0,
+                b: 1.0,
+                a: 1.0,
+            };
+            rt.Clear(Some(&clear));
+
+            self.draw(rt, w as f32, h as f32, false);
+
+            let _ = rt.EndDraw(None, None);
+        }
+    }
+
+    fn create_render_target(&self, hwnd: HWND, width: i32, height: i32) -> Option<ID2D1HwndRenderTarget> {
+        unsafe {
+            let render_props = D2D1_RENDER_TARGET_PROPERTIES {
+                r#type: D2D1_RENDER_TARGET_TYPE_DEFAULT,
+                pixelFormat: D2D1_PIXEL_FORMAT {
+                    format: DXGI_FORMAT_B8G8R8A8_UNORM,
+                    alphaMode: D2D1_ALPHA_MODE_PREMULTIPLIED,
+                },
+                ..Default::default()
+            };
+
+            let hwnd_props = D2D1_HWND_RENDER_TARGET_PROPERTIES {
+                hwnd,
+                pixelSize: windows::Win32::Graphics::Direct2D::Common::D2D_SIZE_U {
+                    width: width.max(1) as u32,
+                    height: height.max(1) as u32,
+                },
+                presentOptions: D2D1_PRESENT_OPTIONS_IMMEDIATELY,
+            };
+
+            self.d2d_factory.CreateHwndRenderTarget(&render_props, &hwnd_props).ok()
+        }
+    }
+
+    /// Draw the background, every monitor and the focus dot onto a cleared
+    /// target. The background is only translucent with per-pixel alpha.
+    unsafe fn draw(&self, rt: &ID2D1RenderTarget, w: f32, h: f32, per_pixel: bool) {
+        let background = if per_pixel { BACKGROUND } else { D2D1_COLOR_F { a: 1.0, ..BACKGROUND } };
+        let Ok(bg_brush) = rt.CreateSolidColorBrush(&background, None) else { return };
+        let Ok(inactive_brush) = rt.CreateSolidColorBrush(&INACTIVE, None) else { return };
+        let Ok(active_brush) = rt.CreateSolidColorBrush(&self.active_color, None) else { return };
+
+        let rounded = D2D1_ROUNDED_RECT {
+            rect: D2D_RECT_F { left: 0.0, top: 0.0, right: w, bottom: h },
+            radiusX: CORNER_RADIUS,
+            radiusY: CORNER_RADIUS,
+        };
+        rt.FillRoundedRectangle(&rounded, &bg_brush);
+
+        let scale = map_scale(&self.bounds, self.width);
+        for (i, mon) in self.monitor_rects.iter().enumerate() {
+            let (left, top) = to_map(mon.left, mon.top, &self.bounds, scale);
+            let (right, bottom) = to_map(mon.right, mon.bottom, &self.bounds, scale);
+            let cell = D2D_RECT_F {
+                left: left + MONITOR_GAP,
+                top: top + MONITOR_GAP,
+                right: right - MONITOR_GAP,
+                bottom: bottom - MONITOR_GAP,
+            };
+            let brush = if i == self.active { &active_brush } else { &inactive_brush };
+            rt.FillRectangle(&cell, brush);
+        }
+
+        if let Some((x, y)) = self.dot {
+            let Ok(dot_brush) = rt.CreateSolidColorBrush(&DOT, None) else { return };
+            let Ok(outline_brush) = rt.CreateSolidColorBrush(&DOT_OUTLINE, None) else { return };
+            let mut dot = D2D1_ELLIPSE { radiusX: DOT_RADIUS, radiusY: DOT_RADIUS, ..Default::default() };
+            dot.point.X = x as f32;
+            dot.point.Y = y as f32;
+            rt.FillEllipse(&dot, &dot_brush);
+            rt.DrawEllipse(&dot, &outline_brush, 1.0, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> RECT {
+        RECT { left, top, right, bottom }
+    }
+
+    #[test]
+    fn layout_keeps_relative_positions() {
+        // 1440p on the left, 1080p on the right, bottoms aligned
+        let monitors = [rect(-2560, -360, 0, 1080), rect(0, 0, 1920, 1080)];
+        let bounds = layout_bounds(&monitors);
+        assert_eq!(bounds, rect(-2560, -360, 1920, 1080));
+
+        let scale = map_scale(&bounds, 124);
+        assert!((scale - 112.0 / 4480.0).abs() < 1e-6);
+        assert_eq!(map_height(&bounds, 124), 48);
+        assert_eq!(to_map(0, 0, &bounds, scale), (PADDING + 64.0, PADDING + 9.0));
+    }
+
+    #[test]
+    fn layout_entirely_at_negative_coordinates() {
+        // Two monitors stacked above and left of the primary's origin
+        let monitors = [rect(-1920, -2160, 0, -1080), rect(-1920, -1080, 0, 0)];
+        let bounds = layout_bounds(&monitors);
+        assert_eq!(bounds, rect(-1920, -2160, 0, 0));
+
+        let scale = map_scale(&bounds, 124);
+        assert_eq!(to_map(-1920, -2160, &bounds, scale), (PADDING, PADDING));
+        let (right, bottom) = to_map(0, 0, &bounds, scale);
+        assert!((right - (124.0 - PADDING)).abs() < 1e-4);
+        assert_eq!(map_height(&bounds, 124), (bottom + PADDING).ceil() as i32);
+        // Seam between the monitors sits halfway down
+        let (_, seam) = to_map(-1920, -1080, &bounds, scale);
+        assert!((seam - (PADDING + bottom) / 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn window_straddling_two_monitors_puts_the_dot_on_the_seam() {
+        let monitors = [rect(-1920, 0, 0, 1080), rect(0, 0, 1920, 1080)];
+        let bounds = layout_bounds(&monitors);
+        let scale = map_scale(&bounds, 124);
+
+        let straddling = rect(-500, 200, 500, 800);
+        let (x, y) = dot_position(&straddling, &bounds, scale);
+        let (seam, _) = to_map(0, 0, &bounds, scale);
+        assert_eq!(x, seam.round() as i32);
+        assert_eq!(y, to_map(0, 500, &bounds, scale).1.round() as i32);
+    }
+
+    #[test]
+    fn dot_center_rounds_toward_the_window_not_the_origin() {
+        // Integer halving of a negative sum would pull the center right
+        let bounds = rect(-1921, 0, 1, 1);
+        let (x, _) = dot_position(&rect(-1921, 0, -1918, 1), &bounds, 1.0);
+        assert_eq!(x, (PADDING + 1.0) as i32);
+    }
+
+    #[test]
+    fn degenerate_layout_does_not_divide_by_zero() {
+        let bounds = rect(0, 0, 0, 0);
+        assert!(map_scale(&bounds, 124).is_finite());
+        assert_eq!(map_height(&bounds, 124), (2.0 * PADDING).ceil() as i32);
+        assert_eq!(map_scale(&rect(0, 0, 1920, 1080), 0), 0.0);
+    }
+}
@@ -1,4 +1,11 @@
+pub mod animation;
 pub mod border;
 pub mod flash;
 pub mod indicator;
+pub mod locator;
+pub mod minimap;
+pub mod reveal;
+pub mod spotlight;
+pub mod surface;
+pub mod title_tab;
 pub mod window;
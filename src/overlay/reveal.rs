@@ -0,0 +1,564 @@
+use std::time::{Duration, Instant};
+
+use windows::Win32::Foundation::{HWND, RECT};
+use windows::Win32::Graphics::Direct2D::Common::{
+    D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_COLOR_F, D2D1_PIXEL_FORMAT, D2D_RECT_F,
+};
+use windows::Win32::Graphics::Direct2D::{
+    D2D1CreateFactory, ID2D1Factory, ID2D1HwndRenderTarget, ID2D1RenderTarget,
+    ID2D1SolidColorBrush, D2D1_DRAW_TEXT_OPTIONS_CLIP, D2D1_FACTORY_TYPE_SINGLE_THREADED,
+    D2D1_HWND_RENDER_TARGET_PROPERTIES, D2D1_PRESENT_OPTIONS_IMMEDIATELY,
+    D2D1_RENDER_TARGET_PROPERTIES, D2D1_RENDER_TARGET_TYPE_DEFAULT, D2D1_ROUNDED_RECT,
+};
+use windows::Win32::Graphics::DirectWrite::{
+    DWriteCreateFactory, IDWriteFactory, IDWriteTextFormat, DWRITE_FACTORY_TYPE_SHARED,
+    DWRITE_FONT_STRETCH_NORMAL, DWRITE_FONT_STYLE_NORMAL, DWRITE_FONT_WEIGHT,
+    DWRITE_FONT_WEIGHT_BOLD, DWRITE_FONT_WEIGHT_NORMAL, DWRITE_FONT_WEIGHT_SEMI_BOLD,
+    DWRITE_MEASURING_MODE_NATURAL, DWRITE_PARAGRAPH_ALIGNMENT_CENTER, DWRITE_TEXT_ALIGNMENT_CENTER,
+    DWRITE_TEXT_ALIGNMENT_LEADING, DWRITE_TRIMMING, DWRITE_TRIMMING_GRANULARITY_CHARACTER,
+    DWRITE_WORD_WRAPPING_NO_WRAP,
+};
+use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8A8_UNORM;
+
+use crate::config::{BorderColor, RenderMode};
+use crate::overlay::animation;
+use crate::overlay::surface::{self, LayeredSurface};
+use crate::overlay::window;
+
+const CARD_W: i32 = 480;
+/// Height of the number, label and resolution block above the window list.
+const HEADER_H: i32 = 172;
+const ROW_H: i32 = 22;
+/// Rows listed before the rest collapse into a "+N more" line.
+const MAX_ROWS: usize = 15;
+const EXE_COLUMN_W: f32 = 140.0;
+const PADDING: f32 = 16.0;
+const CORNER_RADIUS: f32 = 12.0;
+const FADE_OUT: Duration = Duration::from_millis(250);
+
+const BACKGROUND: D2D1_COLOR_F = D2D1_COLOR_F {
+    r: 0.08,
+    g: 0.08,
+    b: 0.08,
+    a: 0.85,
+};
+const TEXT_COLOR: D2D1_COLOR_F = D2D1_COLOR_F {
+    r: 1.0,
+    g: 1.0,
+    b: 1.0,
+    a: 1.0,
+};
+const DETAIL_COLOR: D2D1_COLOR_F = D2D1_COLOR_F {
+    r: 0.75,
+    g: 0.75,
+    b: 0.75,
+    a: 1.0,
+};
+
+/// What one monitor's card shows.
+pub struct CardInfo {
+    pub number: usize,
+    pub label: String,
+    pub width: i32,
+    pub height: i32,
+    /// Whether the focused window is on this monitor.
+    pub has_focus: bool,
+    /// Top-level windows on this monitor, topmost first.
+    pub windows: Vec<CardWindow>,
+}
+
+/// One row of a card's window list.
+pub struct CardWindow {
+    pub exe_name: String,
+    pub title: String,
+    pub focused: bool,
+}
+
+/// Large identify card centered on each monitor, shown while the reveal
+/// hotkey is held and faded out on release.
+pub struct RevealOverlay {
+    cards: Vec<Card>,
+    d2d_factory: ID2D1Factory,
+    number_format: IDWriteTextFormat,
+    label_format: IDWriteTextFormat,
+    detail_format: IDWriteTextFormat,
+    /// Left-aligned format for the window list rows.
+    row_format: IDWriteTextFormat,
+    accent: D2D1_COLOR_F,
+    fade_start: Option<Instant>,
+}
+
+struct Card {
+    hwnd: HWND,
+    monitor_rect: RECT,
+    rect: RECT,
+    render_target: Option<ID2D1HwndRenderTarget>,
+    /// Per-pixel alpha surface; None while on the color key.
+    surface: Option<LayeredSurface>,
+}
+
+impl RevealOverlay {
+    pub fn new(
+        monitor_rects: &[RECT],
+        accent: &BorderColor,
+        render_mode: RenderMode,
+    ) -> Option<Self> {
+        let d2d_factory: ID2D1Factory =
+            unsafe { D2D1CreateFactory(D2D1_FACTORY_TYPE_SINGLE_THREADED, None).ok()? };
+        let dwrite_factory: IDWriteFactory =
+            unsafe { DWriteCreateFactory(DWRITE_FACTORY_TYPE_SHARED).ok()? };
+
+        let mut cards = Vec::with_capacity(monitor_rects.len());
+        for (i, mon) in monitor_rects.iter().enumerate() {
+            let class_name = format!("WhereIsMyWindowReveal{}", i);
+            let height = card_height(0);
+            let hwnd = window::create_overlay_window(&class_name, CARD_W, height)?;
+            let rect = card_rect(mon, height);
+            window::reposition_overlay(hwnd, &rect);
+            let surface = surface::attach(hwnd, &d2d_factory, render_mode == RenderMode::PerPixel);
+            cards.push(Card {
+                hwnd,
+                monitor_rect: *mon,
+                rect,
+                render_target: None,
+                surface,
+            });
+        }
+
+        Some(Self {
+            cards,
+            d2d_factory,
+            number_format: text_format(&dwrite_factory, DWRITE_FONT_WEIGHT_BOLD, 72.0)?,
+            label_format: text_format(&dwrite_factory, DWRITE_FONT_WEIGHT_SEMI_BOLD, 20.0)?,
+            detail_format: text_format(&dwrite_factory, DWRITE_FONT_WEIGHT_NORMAL, 14.0)?,
+            row_format: row_format(&dwrite_factory)?,
+            accent: D2D1_COLOR_F {
+                r: accent.r,
+                g: accent.g,
+                b: accent.b,
+                a: 1.0,
+            },
+            fade_start: None,
+        })
+    }
+
+    /// Render and show every card; `infos` is in monitor order.
+    pub fn show(&mut self, infos: &[CardInfo]) {
+        self.fade_start = None;
+        for i in 0..self.cards.len() {
+            let Some(info) = infos.get(i) else { break };
+            if self.render(i, info) {
+                window::bring_to_front(self.cards[i].hwnd);
+            }
+        }
+    }
+
+    /// Start fading the cards out.
+    pub fn release(&mut self) {
+        if self.fade_start.is_none() && self.cards.iter().any(|c| window::is_visible(c.hwnd)) {
+            self.fade_start = Some(Instant::now());
+        }
+    }
+
+    /// Whether a fade is running and needs `tick` calls.
+    pub fn is_animating(&self) -> bool {
+        self.fade_start.is_some()
+    }
+
+    /// Advance the fade by one frame. Returns false once the cards are gone.
+    pub fn tick(&mut self) -> bool {
+        let Some(start) = self.fade_start else {
+            return false;
+        };
+        let elapsed = Instant::now().saturating_duration_since(start);
+        if elapsed >= FADE_OUT {
+            self.hide();
+            return false;
+        }
+        let alpha = (animation::fade_alpha(elapsed, FADE_OUT) * 255.0).round() as u8;
+        for card in &self.cards {
+            card.apply_alpha(alpha);
+        }
+        true
+    }
+
+    pub fn hide(&mut self) {
+        self.fade_start = None;
+        for card in &self.cards {
+            window::hide_overlay(card.hwnd);
+        }
+    }
+
+    pub fn set_accent(&mut self, color: &BorderColor) {
+        self.accent = D2D1_COLOR_F {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+            a: 1.0,
+        };
+    }
+
+    /// Switch all cards between per-pixel alpha and the color-key fallback.
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        self.hide();
+        for card in &mut self.cards {
+            card.render_target = None;
+            card.surface =
+                surface::attach(card.hwnd, &self.d2d_factory, mode == RenderMode::PerPixel);
+        }
+    }
+
+    pub fn hwnd_list(&self) -> Vec<isize> {
+        self.cards.iter().map(|c| c.hwnd.0 as isize).collect()
+    }
+
+    fn render(&mut self, index: usize, info: &CardInfo) -> bool {
+        let card = &mut self.cards[index];
+        let rect = card_rect(&card.monitor_rect, card_height(info.windows.len()));
+        if rect != card.rect {
+            // The window list changed length; HwndRenderTarget has a fixed size
+            card.rect = rect;
+            card.render_target = None;
+            window::reposition_overlay(card.hwnd, &rect);
+        }
+        let hwnd = card.hwnd;
+        let (w, h) = (rect.right - rect.left, rect.bottom - rect.top);
+
+        if let Some(mut layered) = self.cards[index].surface.take() {
+            let drawn = layered.draw(w, h, |rt| unsafe {
+                self.draw(rt, info, w as f32, h as f32, true)
+            });
+            let presented = drawn && layered.present(hwnd, &rect, 255);
+            self.cards[index].surface = Some(layered);
+            return presented;
+        }
+
+        if self.cards[index].render_target.is_none() {
+            self.cards[index].render_target = self.create_render_target(hwnd, w, h);
+        }
+        let Some(rt) = &self.cards[index].render_target else {
+            return false;
+        };
+
+        unsafe {
+            rt.BeginDraw();
+
+            let clear = D2D1_COLOR_F {
+                r: 1.0,
+                g: 0.0,
+                b: 1.0,
+                a: 1.0,
+            };
+            rt.Clear(Some(&clear));
+
+            self.draw(rt, info, w as f32, h as f32, false);
+
+            if rt.EndDraw(None, None).is_err() {
+                return false;
+            }
+        }
+        window::set_colorkey_alpha(hwnd, 255);
+        true
+    }
+
+    fn create_render_target(
+        &self,
+        hwnd: HWND,
+        width: i32,
+        height: i32,
+    ) -> Option<ID2D1HwndRenderTarget> {
+        unsafe {
+            let render_props = D2D1_RENDER_TARGET_PROPERTIES {
+                r#type: D2D1_RENDER_TARGET_TYPE_DEFAULT,
+                pixelFormat: D2D1_PIXEL_FORMAT {
+                    format: DXGI_FORMAT_B8G8R8A8_UNORM,
+                    alphaMode: D2D1_ALPHA_MODE_PREMULTIPLIED,
+                },
+                ..Default::default()
+            };
+
+            let hwnd_props = D2D1_HWND_RENDER_TARGET_PROPERTIES {
+                hwnd,
+                pixelSize: windows::Win32::Graphics::Direct2D::Common::D2D_SIZE_U {
+                    width: width.max(1) as u32,
+                    height: height.max(1) as u32,
+                },
+                presentOptions: D2D1_PRESENT_OPTIONS_IMMEDIATELY,
+            };
+
+            self.d2d_factory
+                .CreateHwndRenderTarget(&render_props, &hwnd_props)
+                .ok()
+        }
+    }
+
+    /// Draw one card onto a cleared target: the monitor number in the accent
+    /// color on the focused monitor, then label, resolution and the window
+    /// list with the focused window highlighted.
+    unsafe fn draw(
+        &self,
+        rt: &ID2D1RenderTarget,
+        info: &CardInfo,
+        w: f32,
+        h: f32,
+        per_pixel: bool,
+    ) {
+        let background = if per_pixel {
+            BACKGROUND
+        } else {
+            D2D1_COLOR_F {
+                a: 1.0,
+                ..BACKGROUND
+            }
+        };
+        let number_color = if info.has_focus {
+            self.accent
+        } else {
+            TEXT_COLOR
+        };
+        let Ok(bg_brush) = rt.CreateSolidColorBrush(&background, None) else {
+            return;
+        };
+        let Ok(number_brush) = rt.CreateSolidColorBrush(&number_color, None) else {
+            return;
+        };
+        let Ok(text_brush) = rt.CreateSolidColorBrush(&TEXT_COLOR, None) else {
+            return;
+        };
+        let Ok(detail_brush) = rt.CreateSolidColorBrush(&DETAIL_COLOR, None) else {
+            return;
+        };
+        let highlight = D2D1_COLOR_F {
+            a: 0.35,
+            ..self.accent
+        };
+        let Ok(highlight_brush) = rt.CreateSolidColorBrush(&highlight, None) else {
+            return;
+        };
+
+        let rounded = D2D1_ROUNDED_RECT {
+            rect: D2D_RECT_F {
+                left: 0.0,
+                top: 0.0,
+                right: w,
+                bottom: h,
+            },
+            radiusX: CORNER_RADIUS,
+            radiusY: CORNER_RADIUS,
+        };
+        rt.FillRoundedRectangle(&rounded, &bg_brush);
+
+        let row = |top: f32, bottom: f32| D2D_RECT_F {
+            left: PADDING,
+            top,
+            right: w - PADDING,
+            bottom,
+        };
+        let lines = [
+            (
+                info.number.to_string(),
+                &self.number_format,
+                &number_brush,
+                row(PADDING, 110.0),
+            ),
+            (
+                info.label.clone(),
+                &self.label_format,
+                &text_brush,
+                row(110.0, 140.0),
+            ),
+            (
+                format!("{} × {}", info.width, info.height),
+                &self.detail_format,
+                &detail_brush,
+                row(140.0, 164.0),
+            ),
+        ];
+        for (text, format, brush, rect) in lines {
+            let wide: Vec<u16> = text.encode_utf16().collect();
+            rt.DrawText(
+                &wide,
+                format,
+                &rect,
+                brush,
+                D2D1_DRAW_TEXT_OPTIONS_CLIP,
+                DWRITE_MEASURING_MODE_NATURAL,
+            );
+        }
+
+        let (shown, hidden) = visible_rows(info.windows.len());
+        for (i, win) in info.windows.iter().take(shown).enumerate() {
+            let top = (HEADER_H + i as i32 * ROW_H) as f32;
+            let line = row(top, top + ROW_H as f32);
+            if win.focused {
+                let pill = D2D1_ROUNDED_RECT {
+                    rect: D2D_RECT_F {
+                        left: line.left - 6.0,
+                        right: line.right + 6.0,
+                        ..line
+                    },
+                    radiusX: 4.0,
+                    radiusY: 4.0,
+                };
+                rt.FillRoundedRectangle(&pill, &highlight_brush);
+            }
+            let exe_rect = D2D_RECT_F {
+                right: line.left + EXE_COLUMN_W - 8.0,
+                ..line
+            };
+            let title_rect = D2D_RECT_F {
+                left: line.left + EXE_COLUMN_W,
+                ..line
+            };
+            self.draw_row(rt, &win.exe_name, &exe_rect, &detail_brush);
+            self.draw_row(rt, &win.title, &title_rect, &text_brush);
+        }
+        let footer = if info.windows.is_empty() {
+            Some("No windows".to_string())
+        } else if hidden > 0 {
+            Some(format!("+{} more", hidden))
+        } else {
+            None
+        };
+        if let Some(text) = footer {
+            let top = (HEADER_H + shown as i32 * ROW_H) as f32;
+            self.draw_row(rt, &text, &row(top, top + ROW_H as f32), &detail_brush);
+        }
+    }
+
+    unsafe fn draw_row(
+        &self,
+        rt: &ID2D1RenderTarget,
+        text: &str,
+        rect: &D2D_RECT_F,
+        brush: &ID2D1SolidColorBrush,
+    ) {
+        let wide: Vec<u16> = text.encode_utf16().collect();
+        rt.DrawText(
+            &wide,
+            &self.row_format,
+            rect,
+            brush,
+            D2D1_DRAW_TEXT_OPTIONS_CLIP,
+            DWRITE_MEASURING_MODE_NATURAL,
+        );
+    }
+}
+
+/// How many list rows fit on a card, and how many are folded into "+N more".
+fn visible_rows(count: usize) -> (usize, usize) {
+    if count <= MAX_ROWS {
+        (count, 0)
+    } else {
+        // The "+N more" line takes the last row's slot
+        (MAX_ROWS - 1, count - (MAX_ROWS - 1))
+    }
+}
+
+/// Card height for a window list of `count` entries. An empty list still
+/// gets one row for the "No windows" line.
+fn card_height(count: usize) -> i32 {
+    let (shown, hidden) = visible_rows(count);
+    let rows = (shown + usize::from(hidden > 0)).max(1) as i32;
+    HEADER_H + rows * ROW_H + PADDING as i32
+}
+
+/// Card of the given height centered on its monitor.
+fn card_rect(monitor: &RECT, height: i32) -> RECT {
+    let left = (monitor.left + monitor.right - CARD_W) / 2;
+    let top = (monitor.top + monitor.bottom - height) / 2;
+    RECT {
+        left,
+        top,
+        right: left + CARD_W,
+        bottom: top + height,
+    }
+}
+
+impl Card {
+    /// Uniform alpha for the whole card, used while fading.
+    fn apply_alpha(&self, alpha: u8) {
+        if self.surface.is_some() {
+            window::set_layered_alpha(self.hwnd, alpha);
+        } else {
+            window::set_colorkey_alpha(self.hwnd, alpha);
+        }
+    }
+}
+
+/// Left-aligned, vertically centered single-line format for list rows.
+fn row_format(factory: &IDWriteFactory) -> Option<IDWriteTextFormat> {
+    let format = text_format(factory, DWRITE_FONT_WEIGHT_NORMAL, 13.0)?;
+    unsafe {
+        let _ = format.SetTextAlignment(DWRITE_TEXT_ALIGNMENT_LEADING);
+    }
+    Some(format)
+}
+
+/// Centered single-line Segoe UI format that ends long text in "…".
+fn text_format(
+    factory: &IDWriteFactory,
+    weight: DWRITE_FONT_WEIGHT,
+    size: f32,
+) -> Option<IDWriteTextFormat> {
+    unsafe {
+        let format = factory
+            .CreateTextFormat(
+                windows::core::w!("Segoe UI"),
+                None,
+                weight,
+                DWRITE_FONT_STYLE_NORMAL,
+                DWRITE_FONT_STRETCH_NORMAL,
+                size,
+                windows::core::w!(""),
+            )
+            .ok()?;
+        let _ = format.SetTextAlignment(DWRITE_TEXT_ALIGNMENT_CENTER);
+        let _ = format.SetParagraphAlignment(DWRITE_PARAGRAPH_ALIGNMENT_CENTER);
+        let _ = format.SetWordWrapping(DWRITE_WORD_WRAPPING_NO_WRAP);
+        let trimming = DWRITE_TRIMMING {
+            granularity: DWRITE_TRIMMING_GRANULARITY_CHARACTER,
+            delimiter: 0,
+            delimiterCount: 0,
+        };
+        if let Ok(ellipsis) = factory.CreateEllipsisTrimmingSign(&format) {
+            let _ = format.SetTrimming(&trimming, &ellipsis);
+        }
+        Some(format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_lists_show_every_row() {
+        assert_eq!(visible_rows(0), (0, 0));
+        assert_eq!(visible_rows(MAX_ROWS), (MAX_ROWS, 0));
+    }
+
+    #[test]
+    fn long_lists_fold_into_more_line() {
+        assert_eq!(visible_rows(MAX_ROWS + 1), (MAX_ROWS - 1, 2));
+        assert_eq!(card_height(MAX_ROWS + 10), card_height(MAX_ROWS));
+    }
+
+    #[test]
+    fn empty_list_keeps_one_row() {
+        assert_eq!(card_height(0), card_height(1));
+    }
+
+    #[test]
+    fn card_is_centered_on_monitor() {
+        let monitor = RECT {
+            left: 1920,
+            top: 0,
+            right: 3840,
+            bottom: 1080,
+        };
+        let rect = card_rect(&monitor, 200);
+        assert_eq!(rect.left + rect.right, monitor.left + monitor.right);
+        assert_eq!(rect.top + rect.bottom, monitor.top + monitor.bottom);
+    }
+}
@@ -0,0 +1,164 @@
+use windows::Win32::Foundation::{HWND, RECT};
+use windows::Win32::Graphics::Direct2D::Common::{
+    D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_COLOR_F, D2D1_PIXEL_FORMAT,
+};
+use windows::Win32::Graphics::Direct2D::{
+    D2D1CreateFactory, ID2D1Factory,
+    D2D1_FACTORY_TYPE_SINGLE_THREADED, D2D1_HWND_RENDER_TARGET_PROPERTIES,
+    D2D1_PRESENT_OPTIONS_IMMEDIATELY, D2D1_RENDER_TARGET_PROPERTIES,
+    D2D1_RENDER_TARGET_TYPE_DEFAULT,
+};
+use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8A8_UNORM;
+use windows::Win32::Graphics::Gdi::{
+    CombineRgn, CreateRectRgn, CreateRoundRectRgn, DeleteObject, SetWindowRgn, RGN_DIFF,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN,
+    SM_YVIRTUALSCREEN,
+};
+
+use crate::overlay::window;
+
+/// Dim layer over every monitor with a hole cut out for the focused window.
+///
+/// The window is filled black once and dimmed with whole-window alpha; following
+/// the focused window only changes its window region, so moves cost no rendering.
+pub struct SpotlightOverlay {
+    pub hwnd: HWND,
+    factory: ID2D1Factory,
+    /// Virtual screen rect the window currently covers.
+    screen_rect: RECT,
+    /// Cut-out (screen coordinates) and corner radius of the current region.
+    last_cutout: (RECT, f32),
+}
+
+impl SpotlightOverlay {
+    pub fn new(opacity: f32) -> Option<Self> {
+        let hwnd = window::create_overlay_window("WhereIsMyWindowSpotlight", 1, 1)?;
+        let factory: ID2D1Factory = unsafe {
+            D2D1CreateFactory(D2D1_FACTORY_TYPE_SINGLE_THREADED, None).ok()?
+        };
+
+        window::set_alpha(hwnd, (opacity * 255.0) as u8);
+
+        Some(Self {
+            hwnd,
+            factory,
+            screen_rect: RECT::default(),
+            last_cutout: (RECT::default(), 0.0),
+        })
+    }
+
+    pub fn set_opacity(&self, opacity: f32) {
+        window::set_alpha(self.hwnd, (opacity * 255.0) as u8);
+    }
+
+    /// Dim everything except `cutout`, whose corners are rounded by `corner_radius`.
+    pub fn update(&mut self, cutout: &RECT, corner_radius: f32) {
+        let screen_rect = virtual_screen_rect();
+        let resized = screen_rect != self.screen_rect;
+        if resized {
+            // Monitors were added, removed or rearranged
+            self.screen_rect = screen_rect;
+            window::reposition_overlay(self.hwnd, &screen_rect);
+            self.render(&screen_rect);
+        }
+
+        if resized || self.last_cutout != (*cutout, corner_radius) {
+            self.last_cutout = (*cutout, corner_radius);
+            self.set_region(cutout, corner_radius);
+        }
+
+        if !window::is_visible(self.hwnd) {
+            window::show_overlay(self.hwnd);
+        }
+    }
+
+    pub fn hide(&self) {
+        window::hide_overlay(self.hwnd);
+    }
+
+    /// Clip the window to the virtual screen minus the cut-out.
+    fn set_region(&self, cutout: &RECT, corner_radius: f32) {
+        let origin = (self.screen_rect.left, self.screen_rect.top);
+        let width = self.screen_rect.right - self.screen_rect.left;
+        let height = self.screen_rect.bottom - self.screen_rect.top;
+        let (l, t) = (cutout.left - origin.0, cutout.top - origin.1);
+        let (r, b) = (cutout.right - origin.0, cutout.bottom - origin.1);
+
+        unsafe {
+            let region = CreateRectRgn(0, 0, width, height);
+            let hole = if corner_radius > 0.0 {
+                // Ellipse size is the corner diameter; +1 because the
+                // right/bottom edges of round-rect regions are exclusive
+                let d = (corner_radius * 2.0).round() as i32;
+                CreateRoundRectRgn(l, t, r + 1, b + 1, d, d)
+            } else {
+                CreateRectRgn(l, t, r, b)
+            };
+            CombineRgn(Some(region), Some(region), Some(hole), RGN_DIFF);
+            let _ = DeleteObject(hole.into());
+
+            // The system owns the region after a successful call
+            if SetWindowRgn(self.hwnd, Some(region), true) == 0 {
+                let _ = DeleteObject(region.into());
+            }
+        }
+    }
+
+    fn render(&self, rect: &RECT) {
+        unsafe {
+            let w = (rect.right - rect.left).max(1) as u32;
+            let h = (rect.bottom - rect.top).max(1) as u32;
+
+            let render_props = D2D1_RENDER_TARGET_PROPERTIES {
+                r#type: D2D1_RENDER_TARGET_TYPE_DEFAULT,
+                pixelFormat: D2D1_PIXEL_FORMAT {
+                    format: DXGI_FORMAT_B8G8R8A8_UNORM,
+                    alphaMode: D2D1_ALPHA_MODE_PREMULTIPLIED,
+                },
+                ..Default::default()
+            };
+
+            let hwnd_props = D2D1_HWND_RENDER_TARGET_PROPERTIES {
+                hwnd: self.hwnd,
+                pixelSize: windows::Win32::Graphics::Direct2D::Common::D2D_SIZE_U {
+                    width: w,
+                    height: h,
+                },
+                presentOptions: D2D1_PRESENT_OPTIONS_IMMEDIATELY,
+            };
+
+            let Ok(rt) = self.factory.CreateHwndRenderTarget(&render_props, &hwnd_props) else {
+                return;
+            };
+
+            rt.BeginDraw();
+
+            // Opaque black — window-level LWA_ALPHA controls how dark it gets
+            let color = D2D1_COLOR_F {
+                r: 0.0,
+                g: 0.0,
+                b: 0.0,
+                a: 1.0,
+            };
+            rt.Clear(Some(&color));
+
+            let _ = rt.EndDraw(None, None);
+        }
+    }
+}
+
+/// Bounding rect of all monitors in screen coordinates.
+fn virtual_screen_rect() -> RECT {
+    unsafe {
+        let left = GetSystemMetrics(SM_XVIRTUALSCREEN);
+        let top = GetSystemMetrics(SM_YVIRTUALSCREEN);
+        RECT {
+            left,
+            top,
+            right: left + GetSystemMetrics(SM_CXVIRTUALSCREEN),
+            bottom: top + GetSystemMetrics(SM_CYVIRTUALSCREEN),
+        }
+    }
+}
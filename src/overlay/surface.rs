@@ -0,0 +1,189 @@
+use windows::Win32::Foundation::{COLORREF, HWND, POINT, RECT, SIZE};
+use windows::Win32::Graphics::Direct2D::Common::{
+    D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_COLOR_F, D2D1_PIXEL_FORMAT,
+};
+use windows::Win32::Graphics::Direct2D::{
+    ID2D1DCRenderTarget, ID2D1Factory, ID2D1RenderTarget, D2D1_FEATURE_LEVEL_DEFAULT,
+    D2D1_RENDER_TARGET_PROPERTIES, D2D1_RENDER_TARGET_TYPE_DEFAULT,
+    D2D1_RENDER_TARGET_USAGE_NONE,
+};
+use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8A8_UNORM;
+use windows::Win32::Graphics::Gdi::{
+    CreateCompatibleDC, CreateDIBSection, DeleteDC, DeleteObject, SelectObject, AC_SRC_ALPHA,
+    AC_SRC_OVER, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, BLENDFUNCTION, DIB_RGB_COLORS, HBITMAP,
+    HDC, HGDIOBJ,
+};
+use windows::Win32::UI::WindowsAndMessaging::{UpdateLayeredWindow, ULW_ALPHA};
+
+use crate::overlay::window;
+
+/// Off-screen 32bpp premultiplied bitmap presented with UpdateLayeredWindow.
+///
+/// Unlike the magenta color key, every pixel carries its own alpha, so soft
+/// glow, anti-aliased edges and real translucency work. A window presented
+/// this way must never have SetLayeredWindowAttributes called on it — see
+/// `window::reset_layered` when switching an existing window over.
+pub struct LayeredSurface {
+    render_target: ID2D1DCRenderTarget,
+    mem_dc: HDC,
+    bitmap: HBITMAP,
+    old_bitmap: HGDIOBJ,
+    width: i32,
+    height: i32,
+}
+
+impl LayeredSurface {
+    pub fn new(factory: &ID2D1Factory) -> Option<Self> {
+        unsafe {
+            let props = D2D1_RENDER_TARGET_PROPERTIES {
+                r#type: D2D1_RENDER_TARGET_TYPE_DEFAULT,
+                pixelFormat: D2D1_PIXEL_FORMAT {
+                    format: DXGI_FORMAT_B8G8R8A8_UNORM,
+                    alphaMode: D2D1_ALPHA_MODE_PREMULTIPLIED,
+                },
+                // Draw in physical pixels regardless of monitor scaling
+                dpiX: 96.0,
+                dpiY: 96.0,
+                usage: D2D1_RENDER_TARGET_USAGE_NONE,
+                minLevel: D2D1_FEATURE_LEVEL_DEFAULT,
+            };
+            let render_target = factory.CreateDCRenderTarget(&props).ok()?;
+
+            let mem_dc = CreateCompatibleDC(None);
+            if mem_dc.is_invalid() {
+                return None;
+            }
+
+            Some(Self {
+                render_target,
+                mem_dc,
+                bitmap: HBITMAP::default(),
+                old_bitmap: HGDIOBJ::default(),
+                width: 0,
+                height: 0,
+            })
+        }
+    }
+
+    /// (Re)allocate the backing bitmap if the size changed.
+    fn ensure_size(&mut self, width: i32, height: i32) -> bool {
+        if width == self.width && height == self.height && !self.bitmap.is_invalid() {
+            return true;
+        }
+        unsafe {
+            self.release_bitmap();
+
+            let info = BITMAPINFO {
+                bmiHeader: BITMAPINFOHEADER {
+                    biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                    biWidth: width,
+                    biHeight: -height, // top-down
+                    biPlanes: 1,
+                    biBitCount: 32,
+                    biCompression: BI_RGB.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let mut bits = std::ptr::null_mut();
+            let Ok(bitmap) =
+                CreateDIBSection(Some(self.mem_dc), &info, DIB_RGB_COLORS, &mut bits, None, 0)
+            else {
+                return false;
+            };
+
+            self.old_bitmap = SelectObject(self.mem_dc, bitmap.into());
+            self.bitmap = bitmap;
+            self.width = width;
+            self.height = height;
+        }
+        true
+    }
+
+    unsafe fn release_bitmap(&mut self) {
+        if self.bitmap.is_invalid() {
+            return;
+        }
+        SelectObject(self.mem_dc, self.old_bitmap);
+        let _ = DeleteObject(self.bitmap.into());
+        self.bitmap = HBITMAP::default();
+        self.width = 0;
+        self.height = 0;
+    }
+
+    /// Render a `width`×`height` frame. The surface starts fully transparent;
+    /// `draw` issues D2D calls between BeginDraw and EndDraw.
+    pub fn draw(&mut self, width: i32, height: i32, draw: impl FnOnce(&ID2D1RenderTarget)) -> bool {
+        let (width, height) = (width.max(1), height.max(1));
+        if !self.ensure_size(width, height) {
+            return false;
+        }
+        unsafe {
+            let bounds = RECT { left: 0, top: 0, right: width, bottom: height };
+            if self.render_target.BindDC(self.mem_dc, &bounds).is_err() {
+                return false;
+            }
+
+            self.render_target.BeginDraw();
+            self.render_target.Clear(Some(&D2D1_COLOR_F { r: 0.0, g: 0.0, b: 0.0, a: 0.0 }));
+            draw(&self.render_target);
+            self.render_target.EndDraw(None, None).is_ok()
+        }
+    }
+
+    /// Push the last drawn frame to `hwnd`, moving it to `rect` in the same call.
+    /// `alpha` scales the whole window on top of the per-pixel alpha.
+    pub fn present(&self, hwnd: HWND, rect: &RECT, alpha: u8) -> bool {
+        let position = POINT { x: rect.left, y: rect.top };
+        let size = SIZE { cx: self.width, cy: self.height };
+        let source = POINT { x: 0, y: 0 };
+        let blend = blend_function(alpha);
+        unsafe {
+            UpdateLayeredWindow(
+                hwnd,
+                None,
+                Some(&position),
+                Some(&size),
+                Some(self.mem_dc),
+                Some(&source),
+                COLORREF(0),
+                Some(&blend),
+                ULW_ALPHA,
+            )
+            .is_ok()
+        }
+    }
+}
+
+impl Drop for LayeredSurface {
+    fn drop(&mut self) {
+        unsafe {
+            self.release_bitmap();
+            let _ = DeleteDC(self.mem_dc);
+        }
+    }
+}
+
+/// Switch `hwnd` between per-pixel and color-key compositing.
+/// Returns the surface to draw into, or None when the window ends up on the
+/// color key — either by request or because the surface couldn't be created.
+pub fn attach(hwnd: HWND, factory: &ID2D1Factory, per_pixel: bool) -> Option<LayeredSurface> {
+    window::reset_layered(hwnd);
+    let surface = if per_pixel { LayeredSurface::new(factory) } else { None };
+    if surface.is_none() {
+        if per_pixel {
+            log::warn!("Per-pixel alpha unavailable, falling back to color key");
+        }
+        window::set_colorkey(hwnd);
+    }
+    surface
+}
+
+pub(crate) fn blend_function(alpha: u8) -> BLENDFUNCTION {
+    BLENDFUNCTION {
+        BlendOp: AC_SRC_OVER as u8,
+        BlendFlags: 0,
+        SourceConstantAlpha: alpha,
+        AlphaFormat: AC_SRC_ALPHA as u8,
+    }
+}
@@ -0,0 +1,352 @@
+use windows::Win32::Foundation::{HWND, RECT};
+use windows::Win32::Graphics::Direct2D::Common::{
+    D2D_RECT_F, D2D1_ALPHA_MODE_PREMULTIPLIED, D2D1_COLOR_F, D2D1_PIXEL_FORMAT,
+};
+use windows::Win32::Graphics::Direct2D::{
+    D2D1CreateFactory, D2D1_ROUNDED_RECT, ID2D1Factory, ID2D1HwndRenderTarget, ID2D1RenderTarget,
+    D2D1_DRAW_TEXT_OPTIONS_CLIP, D2D1_FACTORY_TYPE_SINGLE_THREADED,
+    D2D1_HWND_RENDER_TARGET_PROPERTIES, D2D1_PRESENT_OPTIONS_IMMEDIATELY,
+    D2D1_RENDER_TARGET_PROPERTIES, D2D1_RENDER_TARGET_TYPE_DEFAULT,
+};
+use windows::Win32::Graphics::DirectWrite::{
+    DWriteCreateFactory, IDWriteFactory, IDWriteTextFormat, DWRITE_FACTORY_TYPE_SHARED,
+    DWRITE_FONT_STRETCH_NORMAL, DWRITE_FONT_STYLE_NORMAL, DWRITE_FONT_WEIGHT_SEMI_BOLD,
+    DWRITE_MEASURING_MODE_NATURAL, DWRITE_PARAGRAPH_ALIGNMENT_CENTER, DWRITE_TEXT_METRICS,
+    DWRITE_TRIMMING, DWRITE_TRIMMING_GRANULARITY_CHARACTER, DWRITE_WORD_WRAPPING_NO_WRAP,
+};
+use windows::Win32::Graphics::Dxgi::Common::DXGI_FORMAT_B8G8R8A8_UNORM;
+
+use crate::config::{BorderColor, RenderMode};
+use crate::overlay::surface::{self, LayeredSurface};
+use crate::overlay::window;
+
+const TAB_H: i32 = 22;
+const PADDING_X: f32 = 8.0;
+/// Distance from the window's left edge, clear of its rounded corner.
+const INDENT: i32 = 12;
+const CORNER_RADIUS: f32 = 5.0;
+const FONT_SIZE: f32 = 12.0;
+
+const TEXT_COLOR: D2D1_COLOR_F = D2D1_COLOR_F {
+    r: 1.0,
+    g: 1.0,
+    b: 1.0,
+    a: 1.0,
+};
+
+/// Placement of a `width`×`height` tab for a window at `target` whose border is
+/// `border` px thick: sitting on the border's top edge, or tucked just inside
+/// the window when the top edge is at or beyond the top of the monitor.
+/// Returns the tab rect and whether it ended up inside.
+fn tab_rect(target: &RECT, monitor: &RECT, border: i32, width: i32, height: i32) -> (RECT, bool) {
+    let left = target.left + INDENT;
+    let above = target.top - border - height;
+    let (top, inside) = if above < monitor.top {
+        (target.top.max(monitor.top), true)
+    } else {
+        (above, false)
+    };
+    let rect = RECT { left, top, right: left + width, bottom: top + height };
+    (rect, inside)
+}
+
+/// Small label attached to the focused window's border showing which app
+/// (and optionally which monitor) has focus.
+pub struct TitleTab {
+    pub hwnd: HWND,
+    d2d_factory: ID2D1Factory,
+    dwrite_factory: IDWriteFactory,
+    text_format: IDWriteTextFormat,
+    render_target: Option<ID2D1HwndRenderTarget>,
+    /// Per-pixel alpha surface; None while on the color key.
+    surface: Option<LayeredSurface>,
+    color: D2D1_COLOR_F,
+    text: String,
+    rect: RECT,
+    inside: bool,
+}
+
+impl TitleTab {
+    pub fn new(color: &BorderColor, render_mode: RenderMode) -> Option<Self> {
+        let hwnd = window::create_overlay_window("WhereIsMyWindowTitleTab", 1, 1)?;
+        let d2d_factory: ID2D1Factory = unsafe {
+            D2D1CreateFactory(D2D1_FACTORY_TYPE_SINGLE_THREADED, None).ok()?
+        };
+        let dwrite_factory: IDWriteFactory = unsafe {
+            DWriteCreateFactory(DWRITE_FACTORY_TYPE_SHARED).ok()?
+        };
+
+        let text_format = unsafe {
+            let format = dwrite_factory
+                .CreateTextFormat(
+                    windows::core::w!("Segoe UI"),
+                    None,
+                    DWRITE_FONT_WEIGHT_SEMI_BOLD,
+                    DWRITE_FONT_STYLE_NORMAL,
+                    DWRITE_FONT_STRETCH_NORMAL,
+                    FONT_SIZE,
+                    windows::core::w!(""),
+                )
+                .ok()?;
+            let _ = format.SetParagraphAlignment(DWRITE_PARAGRAPH_ALIGNMENT_CENTER);
+            let _ = format.SetWordWrapping(DWRITE_WORD_WRAPPING_NO_WRAP);
+            // Long titles end in "…" instead of being cut mid-glyph
+            let trimming = DWRITE_TRIMMING {
+                granularity: DWRITE_TRIMMING_GRANULARITY_CHARACTER,
+                delimiter: 0,
+                delimiterCount: 0,
+            };
+            if let Ok(ellipsis) = dwrite_factory.CreateEllipsisTrimmingSign(&format) {
+                let _ = format.SetTrimming(&trimming, &ellipsis);
+            }
+            format
+        };
+
+        let surface = surface::attach(hwnd, &d2d_factory, render_mode == RenderMode::PerPixel);
+
+        Some(Self {
+            hwnd,
+            d2d_factory,
+            dwrite_factory,
+            text_format,
+            render_target: None,
+            surface,
+            color: D2D1_COLOR_F { r: color.r, g: color.g, b: color.b, a: 1.0 },
+            text: String::new(),
+            rect: RECT::default(),
+            inside: false,
+        })
+    }
+
+    pub fn set_color(&mut self, color: &BorderColor) {
+        let color = D2D1_COLOR_F { r: color.r, g: color.g, b: color.b, a: 1.0 };
+        if self.color == color {
+            return;
+        }
+        self.color = color;
+        self.rect = RECT::default();
+    }
+
+    /// Switch between per-pixel alpha and the color-key fallback.
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        self.render_target = None;
+        self.surface = surface::attach(self.hwnd, &self.d2d_factory, mode == RenderMode::PerPixel);
+        self.rect = RECT::default();
+    }
+
+    /// Show `text` on the border of a window at `target`. The tab never gets
+    /// wider than the window; longer text is truncated with an ellipsis.
+    pub fn show(&mut self, text: &str, target: &RECT, monitor: &RECT, border: i32) {
+        let max_width = (target.right - target.left - 2 * INDENT).max(0);
+        let width = (self.measure(text) + 2.0 * PADDING_X).ceil() as i32;
+        let width = width.min(max_width);
+        if width <= 2 * PADDING_X as i32 {
+            // Window too narrow for any text
+            self.hide();
+            return;
+        }
+
+        let (rect, inside) = tab_rect(target, monitor, border, width, TAB_H);
+        let same_size = rect.right - rect.left == self.rect.right - self.rect.left
+            && rect.bottom - rect.top == self.rect.bottom - self.rect.top;
+
+        if same_size && text == self.text && inside == self.inside {
+            if rect != self.rect {
+                self.rect = rect;
+                window::reposition_overlay(self.hwnd, &rect);
+            }
+        } else {
+            self.text = text.to_string();
+            self.rect = rect;
+            self.inside = inside;
+            if !self.render() {
+                self.hide();
+                return;
+            }
+        }
+
+        if !window::is_visible(self.hwnd) {
+            window::bring_to_front(self.hwnd);
+        }
+    }
+
+    pub fn bring_to_front(&self) {
+        if window::is_visible(self.hwnd) {
+            window::bring_to_front(self.hwnd);
+        }
+    }
+
+    pub fn hide(&self) {
+        window::hide_overlay(self.hwnd);
+    }
+
+    /// Natural width of `text` in px, before truncation.
+    fn measure(&self, text: &str) -> f32 {
+        let wide: Vec<u16> = text.encode_utf16().collect();
+        unsafe {
+            let Ok(layout) = self.dwrite_factory.CreateTextLayout(
+                &wide,
+                &self.text_format,
+                f32::MAX,
+                TAB_H as f32,
+            ) else {
+                return 0.0;
+            };
+            let mut metrics = DWRITE_TEXT_METRICS::default();
+            if layout.GetMetrics(&mut metrics).is_err() {
+                return 0.0;
+            }
+            metrics.widthIncludingTrailingWhitespace
+        }
+    }
+
+    fn render(&mut self) -> bool {
+        let w = self.rect.right - self.rect.left;
+        let h = self.rect.bottom - self.rect.top;
+
+        if let Some(mut layered) = self.surface.take() {
+            let drawn = layered.draw(w, h, |rt| unsafe { self.draw(rt, w as f32, h as f32) });
+            let presented = drawn && layered.present(self.hwnd, &self.rect, 255);
+            self.surface = Some(layered);
+            return presented;
+        }
+
+        window::reposition_overlay(self.hwnd, &self.rect);
+        self.render_target = None;
+        self.create_render_target(w as u32, h as u32);
+        let Some(rt) = &self.render_target else { return false };
+
+        unsafe {
+            rt.BeginDraw();
+
+            let clear = D2D1_COLOR_F {
+                r: 1.0,
+                g: 0.0,
+                b: 1.0,
+                a: 1.0,
+            };
+            rt.Clear(Some(&clear));
+
+            self.draw(rt, w as f32, h as f32);
+
+            rt.EndDraw(None, None).is_ok()
+        }
+    }
+
+    fn create_render_target(&mut self, width: u32, height: u32) {
+        unsafe {
+            let render_props = D2D1_RENDER_TARGET_PROPERTIES {
+                r#type: D2D1_RENDER_TARGET_TYPE_DEFAULT,
+                pixelFormat: D2D1_PIXEL_FORMAT {
+                    format: DXGI_FORMAT_B8G8R8A8_UNORM,
+                    alphaMode: D2D1_ALPHA_MODE_PREMULTIPLIED,
+                },
+                ..Default::default()
+            };
+
+            let hwnd_props = D2D1_HWND_RENDER_TARGET_PROPERTIES {
+                hwnd: self.hwnd,
+                pixelSize: windows::Win32::Graphics::Direct2D::Common::D2D_SIZE_U {
+                    width: width.max(1),
+                    height: height.max(1),
+                },
+                presentOptions: D2D1_PRESENT_OPTIONS_IMMEDIATELY,
+            };
+
+            if let Ok(rt) = self
+                .d2d_factory
+                .CreateHwndRenderTarget(&render_props, &hwnd_props)
+            {
+                self.render_target = Some(rt);
+            }
+        }
+    }
+
+    /// Draw the tab onto a cleared target. Corners touching the border stay
+    /// square so the tab reads as part of the frame.
+    unsafe fn draw(&self, rt: &ID2D1RenderTarget, w: f32, h: f32) {
+        let Ok(bg_brush) = rt.CreateSolidColorBrush(&self.color, None) else { return };
+        let Ok(text_brush) = rt.CreateSolidColorBrush(&TEXT_COLOR, None) else { return };
+
+        let rounded = D2D1_ROUNDED_RECT {
+            rect: D2D_RECT_F { left: 0.0, top: 0.0, right: w, bottom: h },
+            radiusX: CORNER_RADIUS,
+            radiusY: CORNER_RADIUS,
+        };
+        rt.FillRoundedRectangle(&rounded, &bg_brush);
+        let square_half = if self.inside {
+            D2D_RECT_F { left: 0.0, top: 0.0, right: w, bottom: h / 2.0 }
+        } else {
+            D2D_RECT_F { left: 0.0, top: h / 2.0, right: w, bottom: h }
+        };
+        rt.FillRectangle(&square_half, &bg_brush);
+
+        let text_wide: Vec<u16> = self.text.encode_utf16().collect();
+        let layout_rect = D2D_RECT_F {
+            left: PADDING_X,
+            top: 0.0,
+            right: w - PADDING_X,
+            bottom: h,
+        };
+        rt.DrawText(
+            &text_wide,
+            &self.text_format,
+            &layout_rect,
+            &text_brush,
+            D2D1_DRAW_TEXT_OPTIONS_CLIP,
+            DWRITE_MEASURING_MODE_NATURAL,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONITOR: RECT = RECT { left: 0, top: 0, right: 1920, bottom: 1080 };
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> RECT {
+        RECT { left, top, right, bottom }
+    }
+
+    #[test]
+    fn tab_sits_on_the_border_above_the_window() {
+        let (tab, inside) = tab_rect(&rect(100, 200, 900, 700), &MONITOR, 4, 150, TAB_H);
+        assert!(!inside);
+        assert_eq!(tab, rect(100 + INDENT, 200 - 4 - TAB_H, 100 + INDENT + 150, 200 - 4));
+    }
+
+    #[test]
+    fn tab_tucks_inside_when_it_would_leave_the_monitor() {
+        let (tab, inside) = tab_rect(&rect(100, 20, 900, 700), &MONITOR, 4, 150, TAB_H);
+        assert!(inside);
+        assert_eq!((tab.top, tab.bottom), (20, 20 + TAB_H));
+    }
+
+    #[test]
+    fn tab_fits_exactly_above_at_the_monitor_top() {
+        let top = 4 + TAB_H;
+        let (tab, inside) = tab_rect(&rect(100, top, 900, 700), &MONITOR, 4, 150, TAB_H);
+        assert!(!inside);
+        assert_eq!(tab.top, MONITOR.top);
+    }
+
+    #[test]
+    fn tab_of_window_above_the_monitor_is_clamped_to_its_top() {
+        let (tab, inside) = tab_rect(&rect(-8, -8, 1928, 1048), &MONITOR, 0, 150, TAB_H);
+        assert!(inside);
+        assert_eq!((tab.left, tab.top), (-8 + INDENT, 0));
+    }
+
+    #[test]
+    fn tab_follows_monitors_at_negative_coordinates() {
+        let monitor = rect(-1920, -300, 0, 780);
+        let (tab, inside) = tab_rect(&rect(-1800, -290, -900, 400), &monitor, 6, 120, TAB_H);
+        assert!(inside);
+        assert_eq!(tab, rect(-1800 + INDENT, -290, -1800 + INDENT + 120, -290 + TAB_H));
+
+        let (tab, inside) = tab_rect(&rect(-1800, 0, -900, 400), &monitor, 6, 120, TAB_H);
+        assert!(!inside);
+        assert_eq!(tab.bottom, -6);
+    }
+}
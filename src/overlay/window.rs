@@ -9,7 +9,8 @@ pub const COLOR_KEY: COLORREF = COLORREF(0x00FF00FF); // RGB(255, 0, 255)
 /// Create a transparent, click-through, topmost overlay window.
 ///
 /// WS_EX_LAYERED | WS_EX_TRANSPARENT guarantees mouse/keyboard pass-through.
-/// Caller must set layered attributes via set_colorkey() or set_alpha().
+/// Caller must set layered attributes via set_colorkey() or set_alpha(),
+/// or hand the window to `surface::attach` for per-pixel alpha.
 pub fn create_overlay_window(class_name: &str, width: i32, height: i32) -> Option<HWND> {
     unsafe {
         let hinstance = GetModuleHandleW(None).ok()?;
@@ -60,6 +61,14 @@ pub fn set_colorkey(hwnd: HWND) {
     }
 }
 
+/// Color-key transparency combined with a uniform alpha for the visible pixels.
+/// Used to fade the border in and out without re-rendering.
+pub fn set_colorkey_alpha(hwnd: HWND, alpha: u8) {
+    unsafe {
+        let _ = SetLayeredWindowAttributes(hwnd, COLOR_KEY, alpha, LWA_COLORKEY | LWA_ALPHA);
+    }
+}
+
 /// Make the entire window fully invisible by combining colorkey with alpha=0.
 /// The window stays visible to the system (not SW_HIDE), so D2D rendering
 /// still works normally. Call set_colorkey() to restore normal visibility.
@@ -77,6 +86,36 @@ pub fn set_alpha(hwnd: HWND, alpha: u8) {
     }
 }
 
+/// Change the uniform alpha of a per-pixel (UpdateLayeredWindow) window
+/// without re-uploading its bitmap.
+pub fn set_layered_alpha(hwnd: HWND, alpha: u8) {
+    let blend = crate::overlay::surface::blend_function(alpha);
+    unsafe {
+        let _ = UpdateLayeredWindow(
+            hwnd,
+            None,
+            None,
+            None,
+            None,
+            None,
+            COLORREF(0),
+            Some(&blend),
+            ULW_ALPHA,
+        );
+    }
+}
+
+/// Drop any layered attributes so the window can switch between
+/// SetLayeredWindowAttributes (color key) and UpdateLayeredWindow (per-pixel)
+/// — Windows refuses the latter once the former has been used.
+pub fn reset_layered(hwnd: HWND) {
+    unsafe {
+        let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE);
+        SetWindowLongW(hwnd, GWL_EXSTYLE, ex_style & !(WS_EX_LAYERED.0 as i32));
+        SetWindowLongW(hwnd, GWL_EXSTYLE, ex_style | WS_EX_LAYERED.0 as i32);
+    }
+}
+
 /// Reposition and resize an overlay window without activating it.
 /// Does NOT change visibility — caller controls show/hide separately.
 pub fn reposition_overlay(hwnd: HWND, rect: &RECT) {
@@ -114,6 +153,10 @@ pub fn hide_overlay(hwnd: HWND) {
     }
 }
 
+pub fn is_visible(hwnd: HWND) -> bool {
+    unsafe { IsWindowVisible(hwnd).as_bool() }
+}
+
 pub fn show_overlay(hwnd: HWND) {
     unsafe {
         let _ = ShowWindow(hwnd, SW_SHOWNOACTIVATE);
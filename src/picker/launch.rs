@@ -0,0 +1,111 @@
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+
+use eframe::egui;
+
+use crate::focus::window_info;
+use crate::monitor::{enumeration, geometry};
+use crate::picker::ui::{PickerApp, PickerEntry};
+
+/// Spawn the window picker as a subprocess, the same way as settings.
+/// The subprocess runs the same exe with `--picker`, activates the chosen
+/// window itself (it owns the foreground at that point) and prints its
+/// handle on stdout so the caller can flash its monitor.
+pub fn open_picker() -> Option<Child> {
+    let exe = match std::env::current_exe() {
+        Ok(p) => p,
+        Err(e) => {
+            log::error!("Failed to get current exe path: {}", e);
+            return None;
+        }
+    };
+
+    match Command::new(exe).arg("--picker").stdout(Stdio::piped()).spawn() {
+        Ok(child) => {
+            log::info!("Picker subprocess spawned (pid={})", child.id());
+            Some(child)
+        }
+        Err(e) => {
+            log::error!("Failed to spawn picker subprocess: {}", e);
+            None
+        }
+    }
+}
+
+/// Check if the picker subprocess has exited.
+/// Returns Some(Some(hwnd)) if a window was chosen, Some(None) if the picker
+/// was dismissed, None if still running.
+pub fn poll_child(child: &mut Child) -> Option<Option<isize>> {
+    match child.try_wait() {
+        Ok(Some(status)) => {
+            let mut output = String::new();
+            if let Some(mut stdout) = child.stdout.take() {
+                let _ = stdout.read_to_string(&mut output);
+            }
+            let chosen = if status.success() { output.trim().parse().ok() } else { None };
+            log::info!("Picker subprocess exited (chosen={:?})", chosen);
+            Some(chosen)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            log::error!("Failed to poll picker subprocess: {}", e);
+            Some(None)
+        }
+    }
+}
+
+/// Run the picker UI as the main process (called with `--picker` flag).
+/// Returns exit code: 0 = a window was activated, 1 = dismissed.
+pub fn run_picker_main() -> i32 {
+    // Snapshot before our own window exists, so it isn't listed
+    let entries = switch_targets();
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default()
+            .with_title("Switch to window")
+            .with_inner_size([560.0, 380.0])
+            .with_always_on_top()
+            .with_resizable(true),
+        centered: true,
+        ..Default::default()
+    };
+
+    let result = eframe::run_native(
+        "WhereIsMyWindow Picker",
+        options,
+        Box::new(move |_cc| Ok(Box::new(PickerApp::new(entries, tx)))),
+    );
+
+    if let Err(e) = result {
+        log::error!("Picker eframe error: {}", e);
+        return 1;
+    }
+
+    let Ok(hwnd) = rx.try_recv() else {
+        return 1;
+    };
+    if !window_info::activate_window(hwnd) {
+        log::warn!("Failed to activate window {:#x}", hwnd);
+        return 1;
+    }
+    println!("{}", hwnd);
+    0
+}
+
+/// Every switchable window with the monitor it is on, in z-order.
+fn switch_targets() -> Vec<PickerEntry> {
+    let monitor_rects: Vec<_> = enumeration::enumerate_monitors()
+        .iter()
+        .map(|m| m.full_rect)
+        .collect();
+    window_info::enumerate_switch_targets()
+        .into_iter()
+        .map(|w| PickerEntry {
+            hwnd: w.hwnd,
+            monitor: geometry::best_monitor_index(&w.rect, &monitor_rects) + 1,
+            exe_name: w.exe_name,
+            title: w.title,
+        })
+        .collect()
+}
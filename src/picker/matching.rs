@@ -0,0 +1,164 @@
+//! Fuzzy matching and ranking for the window picker.
+//!
+//! A query is split on whitespace; every term has to appear in the
+//! candidate as a case-insensitive subsequence. Terms score higher when
+//! their characters are consecutive and start at word boundaries, and
+//! lower the more characters they skip.
+
+const MATCH: i32 = 16;
+/// Extra for a character that continues the previous match.
+const CONSECUTIVE: i32 = 12;
+/// Extra for a character at the start of a word.
+const BOUNDARY: i32 = 8;
+/// Cost per skipped character between two matched characters.
+const GAP: i32 = 1;
+
+/// Score `query` against `candidate`, or None if some term doesn't match.
+/// An empty query matches everything with a score of zero.
+pub fn score(query: &str, candidate: &str) -> Option<i32> {
+    let chars: Vec<char> = candidate.chars().collect();
+    let lower: Vec<char> = chars.iter().map(|&c| fold(c)).collect();
+    query
+        .split_whitespace()
+        .map(|term| score_term(term, &chars, &lower))
+        .sum()
+}
+
+/// Indices of the candidates matching `query`, best first. Equal scores
+/// keep their original order, so an empty query returns the input order.
+pub fn rank<S: AsRef<str>>(query: &str, candidates: &[S]) -> Vec<usize> {
+    let mut scored: Vec<(usize, i32)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| score(query, c.as_ref()).map(|s| (i, s)))
+        .collect();
+    // Stable sort, best score first
+    scored.sort_by_key(|&(_, s)| std::cmp::Reverse(s));
+    scored.into_iter().map(|(i, _)| i).collect()
+}
+
+/// Best alignment of one term, by dynamic programming over
+/// (term character, candidate position).
+fn score_term(term: &str, chars: &[char], lower: &[char]) -> Option<i32> {
+    let needle: Vec<char> = term.chars().map(fold).collect();
+    if needle.len() > lower.len() {
+        return None;
+    }
+
+    // best[j]: best score with the current term character matched at j
+    let mut best: Vec<Option<i32>> = vec![None; lower.len()];
+    for (i, &nc) in needle.iter().enumerate() {
+        let mut next: Vec<Option<i32>> = vec![None; lower.len()];
+        // Best of best[k] + k * GAP over k < j - 1, for the gap transition
+        let mut reach: Option<i32> = None;
+        for j in 0..lower.len() {
+            if j >= 2 {
+                if let Some(prev) = best[j - 2] {
+                    let candidate = prev + (j as i32 - 2) * GAP;
+                    reach = Some(reach.map_or(candidate, |r| r.max(candidate)));
+                }
+            }
+            if lower[j] != nc {
+                continue;
+            }
+            let bonus = MATCH + if is_boundary(chars, j) { BOUNDARY } else { 0 };
+            next[j] = if i == 0 {
+                // Leading skips cost the same as gaps
+                Some(bonus - j as i32 * GAP)
+            } else {
+                let consecutive = j
+                    .checked_sub(1)
+                    .and_then(|k| best[k])
+                    .map(|prev| prev + CONSECUTIVE);
+                let gapped = reach.map(|r| r - (j as i32 - 1) * GAP);
+                match (consecutive, gapped) {
+                    (Some(a), Some(b)) => Some(a.max(b) + bonus),
+                    (Some(a), None) | (None, Some(a)) => Some(a + bonus),
+                    (None, None) => None,
+                }
+            };
+        }
+        best = next;
+    }
+    best.into_iter().flatten().max()
+}
+
+/// Whether `chars[j]` starts a word: the first character, one after a
+/// separator, or an uppercase letter after a lowercase one.
+fn is_boundary(chars: &[char], j: usize) -> bool {
+    let Some(&prev) = j.checked_sub(1).and_then(|k| chars.get(k)) else {
+        return true;
+    };
+    let cur = chars[j];
+    !prev.is_alphanumeric() || (prev.is_lowercase() && cur.is_uppercase())
+}
+
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_query_keeps_order() {
+        let windows = ["b.exe Beta", "a.exe Alpha", "c.exe Gamma"];
+        assert_eq!(rank("", &windows), vec![0, 1, 2]);
+        assert_eq!(rank("   ", &windows), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn non_subsequence_is_rejected() {
+        assert_eq!(score("xyz", "code.exe main.rs"), None);
+        assert_eq!(score("edoc", "code.exe"), None);
+        assert_eq!(score("longer than text", "short"), None);
+    }
+
+    #[test]
+    fn matching_ignores_case() {
+        assert!(score("FIREFOX", "firefox.exe Mozilla Firefox").is_some());
+        assert!(score("mozilla", "firefox.exe Mozilla Firefox").is_some());
+    }
+
+    #[test]
+    fn consecutive_beats_scattered() {
+        let tight = score("code", "code.exe").unwrap();
+        let loose = score("code", "c o d e").unwrap();
+        assert!(tight > loose, "{tight} <= {loose}");
+    }
+
+    #[test]
+    fn word_start_beats_mid_word() {
+        let windows = ["determine.exe Results", "terminal.exe Terminal"];
+        assert_eq!(rank("term", &windows), vec![1, 0]);
+    }
+
+    #[test]
+    fn camel_case_humps_are_boundaries() {
+        let hump = score("ws", "WinSpy").unwrap();
+        let flat = score("ws", "winspy").unwrap();
+        assert!(hump > flat, "{hump} <= {flat}");
+    }
+
+    #[test]
+    fn best_alignment_is_found() {
+        // Greedy matching would take the first "a" and miss "ab" together
+        let aligned = score("ab", "axxab").unwrap();
+        let split = score("ab", "axxxb").unwrap();
+        assert!(aligned > split, "{aligned} <= {split}");
+    }
+
+    #[test]
+    fn every_term_must_match() {
+        let windows = ["chrome.exe GitHub - Pull requests", "chrome.exe Gmail - Inbox"];
+        assert_eq!(rank("chrome git", &windows), vec![0]);
+        assert_eq!(rank("git chrome", &windows), vec![0]);
+    }
+
+    #[test]
+    fn equal_scores_keep_original_order() {
+        let windows = ["notepad.exe a.txt", "notepad.exe b.txt"];
+        assert_eq!(rank("notepad", &windows), vec![0, 1]);
+    }
+}
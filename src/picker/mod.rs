@@ -0,0 +1,3 @@
+pub mod launch;
+pub mod matching;
+pub mod ui;
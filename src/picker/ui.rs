@@ -0,0 +1,132 @@
+use std::sync::mpsc::Sender;
+
+use eframe::egui;
+
+use crate::picker::matching;
+
+/// One switchable window as listed by the picker.
+pub struct PickerEntry {
+    pub hwnd: isize,
+    pub exe_name: String,
+    pub title: String,
+    /// 1-based monitor number, as shown on the badges.
+    pub monitor: usize,
+}
+
+pub struct PickerApp {
+    entries: Vec<PickerEntry>,
+    /// "exe title" per entry, the text the query is matched against.
+    haystacks: Vec<String>,
+    query: String,
+    /// Entry indices matching the query, best first.
+    matches: Vec<usize>,
+    /// Position in `matches` of the highlighted row.
+    selected: usize,
+    /// Scroll the highlighted row into view on the next frame.
+    scroll_to_selected: bool,
+    tx: Sender<isize>,
+}
+
+impl PickerApp {
+    pub fn new(entries: Vec<PickerEntry>, tx: Sender<isize>) -> Self {
+        let haystacks = entries
+            .iter()
+            .map(|e| format!("{} {}", e.exe_name, e.title))
+            .collect();
+        let matches = (0..entries.len()).collect();
+        Self {
+            entries,
+            haystacks,
+            query: String::new(),
+            matches,
+            selected: 0,
+            scroll_to_selected: false,
+            tx,
+        }
+    }
+
+    fn refilter(&mut self) {
+        self.matches = matching::rank(&self.query, &self.haystacks);
+        self.selected = 0;
+        self.scroll_to_selected = true;
+    }
+
+    fn choose(&self, ctx: &egui::Context, index: usize) {
+        let _ = self.tx.send(self.entries[index].hwnd);
+        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+    }
+}
+
+impl eframe::App for PickerApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let (up, down, enter, escape) = ctx.input(|i| {
+            (
+                i.key_pressed(egui::Key::ArrowUp),
+                i.key_pressed(egui::Key::ArrowDown),
+                i.key_pressed(egui::Key::Enter),
+                i.key_pressed(egui::Key::Escape),
+            )
+        });
+        if escape {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            return;
+        }
+        if down && self.selected + 1 < self.matches.len() {
+            self.selected += 1;
+            self.scroll_to_selected = true;
+        }
+        if up && self.selected > 0 {
+            self.selected -= 1;
+            self.scroll_to_selected = true;
+        }
+        if enter {
+            if let Some(&index) = self.matches.get(self.selected) {
+                self.choose(ctx, index);
+                return;
+            }
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let search = ui.add(
+                egui::TextEdit::singleline(&mut self.query)
+                    .hint_text("Type to filter windows")
+                    .desired_width(f32::INFINITY),
+            );
+            // Keep typing going to the search box, even after a row click
+            search.request_focus();
+            if search.changed() {
+                self.refilter();
+            }
+            ui.add_space(6.0);
+
+            if self.matches.is_empty() {
+                ui.label(egui::RichText::new("No matching windows").weak());
+                return;
+            }
+
+            let mut clicked = None;
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for (pos, &index) in self.matches.iter().enumerate() {
+                    let entry = &self.entries[index];
+                    let row = ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(entry.monitor.to_string()).weak().monospace());
+                        let text = format!("{}  —  {}", entry.exe_name, entry.title);
+                        ui.selectable_label(pos == self.selected, text)
+                    });
+                    let label = row.inner;
+                    if label.clicked() {
+                        clicked = Some(index);
+                    }
+                    if pos == self.selected && self.scroll_to_selected {
+                        label.scroll_to_me(None);
+                    }
+                }
+            });
+            self.scroll_to_selected = false;
+
+            if let Some(index) = clicked {
+                self.choose(ctx, index);
+            }
+        });
+    }
+}
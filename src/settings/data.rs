@@ -1,4 +1,9 @@
-use crate::config::{BorderColor, BorderStyle, Config};
+use std::collections::BTreeMap;
+
+use crate::config::{
+    BadgeCorner, BadgeShape, BorderColor, BorderStyle, Config, CornerStyle, Easing, FlashShape,
+    FlashTarget, GlowFalloff, HighContrast, IndicatorMode, MiniMapScope, MonitorConfig, RenderMode, WarpTarget,
+};
 
 /// Plain-data mirror of Config, used for egui editing and channel transport.
 #[derive(Debug, Clone)]
@@ -6,18 +11,73 @@ pub struct SettingsData {
     pub border_enabled: bool,
     pub flash_enabled: bool,
     pub indicator_enabled: bool,
-    pub border_color: [f32; 3],
+    pub theme: String,
+    pub accent_enabled: bool,
+    pub high_contrast: HighContrast,
+    pub border_color: [f32; 4],
     pub border_thickness: f32,
     pub border_style: BorderStyle,
+    pub gradient_stops: Vec<[f32; 4]>,
+    pub gradient_rotate: bool,
+    pub gradient_rotation_ms: u32,
+    pub dash_length: f32,
+    pub dash_gap: f32,
+    pub glow_width: f32,
+    pub glow_layers: u32,
+    pub glow_falloff: GlowFalloff,
+    pub corner_style: CornerStyle,
+    pub corner_radius: f32,
+    pub animate_transitions: bool,
+    pub animation_duration_ms: u32,
+    pub animation_easing: Easing,
     pub flash_duration_ms: u32,
+    pub flash_fade_ms: u32,
     pub flash_opacity: f32,
+    /// Off means the flash follows the border color.
+    pub flash_color_custom: bool,
+    pub flash_color: [f32; 4],
+    pub flash_shape: FlashShape,
+    pub flash_target: FlashTarget,
+    pub flash_same_monitor: bool,
+    pub spotlight_enabled: bool,
+    pub spotlight_opacity: f32,
+    pub title_tab_enabled: bool,
+    pub title_tab_show_title: bool,
+    pub title_tab_show_monitor: bool,
+    pub indicator_mode: IndicatorMode,
+    pub minimap_width: i32,
+    pub minimap_scope: MiniMapScope,
+    pub badge_corner: BadgeCorner,
+    pub badge_offset_x: i32,
+    pub badge_offset_y: i32,
+    pub badge_width: i32,
+    pub badge_height: i32,
+    pub badge_shape: BadgeShape,
+    pub badge_font_size: f32,
+    /// Off means the active badge follows the border color.
+    pub badge_color_custom: bool,
+    pub badge_color: [f32; 4],
+    pub badge_inactive_color: [f32; 4],
+    pub badge_template: String,
     pub reveal_hotkey_enabled: bool,
+    pub picker_hotkey_enabled: bool,
+    pub locator_hotkey_enabled: bool,
+    pub locator_on_shake: bool,
+    pub locator_on_monitor_change: bool,
+    pub warp_on_monitor_change: bool,
+    pub warp_target: WarpTarget,
+    /// Comma-separated for editing; split back into a list by `to_config`.
+    pub warp_exclude: String,
+    pub warp_hotkey_enabled: bool,
     pub auto_start: bool,
     pub poll_interval_ms: u32,
+    pub render_mode: RenderMode,
+    /// Per-monitor overrides; edited in the config file only, carried through as-is.
+    pub monitors: BTreeMap<String, MonitorConfig>,
 }
 
 pub enum SettingsMessage {
-    Apply(SettingsData),
+    Apply(Box<SettingsData>),
     Closed,
 }
 
@@ -27,14 +87,65 @@ impl SettingsData {
             border_enabled: config.border_enabled,
             flash_enabled: config.flash_enabled,
             indicator_enabled: config.indicator_enabled,
-            border_color: [config.border_color.r, config.border_color.g, config.border_color.b],
+            theme: config.theme.clone(),
+            accent_enabled: config.accent_enabled,
+            high_contrast: config.high_contrast,
+            border_color: rgba(config.border_color),
             border_thickness: config.border_thickness,
             border_style: config.border_style,
+            gradient_stops: config.gradient_stops.iter().copied().map(rgba).collect(),
+            gradient_rotate: config.gradient_rotate,
+            gradient_rotation_ms: config.gradient_rotation_ms,
+            dash_length: config.dash_length,
+            dash_gap: config.dash_gap,
+            glow_width: config.glow_width,
+            glow_layers: config.glow_layers,
+            glow_falloff: config.glow_falloff,
+            corner_style: config.corner_style,
+            corner_radius: config.corner_radius,
+            animate_transitions: config.animate_transitions,
+            animation_duration_ms: config.animation_duration_ms,
+            animation_easing: config.animation_easing,
             flash_duration_ms: config.flash_duration_ms,
+            flash_fade_ms: config.flash_fade_ms,
             flash_opacity: config.flash_opacity,
+            flash_color_custom: config.flash_color.is_some(),
+            flash_color: rgba(config.effective_flash_color()),
+            flash_shape: config.flash_shape,
+            flash_target: config.flash_target,
+            flash_same_monitor: config.flash_same_monitor,
+            spotlight_enabled: config.spotlight_enabled,
+            spotlight_opacity: config.spotlight_opacity,
+            title_tab_enabled: config.title_tab_enabled,
+            title_tab_show_title: config.title_tab_show_title,
+            title_tab_show_monitor: config.title_tab_show_monitor,
+            indicator_mode: config.indicator_mode,
+            minimap_width: config.minimap_width,
+            minimap_scope: config.minimap_scope,
+            badge_corner: config.badge_corner,
+            badge_offset_x: config.badge_offset_x,
+            badge_offset_y: config.badge_offset_y,
+            badge_width: config.badge_width,
+            badge_height: config.badge_height,
+            badge_shape: config.badge_shape,
+            badge_font_size: config.badge_font_size,
+            badge_color_custom: config.badge_color.is_some(),
+            badge_color: rgba(config.effective_badge_color()),
+            badge_inactive_color: rgba(config.badge_inactive_color),
+            badge_template: config.badge_template.clone(),
             reveal_hotkey_enabled: config.reveal_hotkey_enabled,
+            picker_hotkey_enabled: config.picker_hotkey_enabled,
+            locator_hotkey_enabled: config.locator_hotkey_enabled,
+            locator_on_shake: config.locator_on_shake,
+            locator_on_monitor_change: config.locator_on_monitor_change,
+            warp_on_monitor_change: config.warp_on_monitor_change,
+            warp_target: config.warp_target,
+            warp_exclude: config.warp_exclude.join(", "),
+            warp_hotkey_enabled: config.warp_hotkey_enabled,
             auto_start: config.auto_start,
             poll_interval_ms: config.poll_interval_ms,
+            render_mode: config.render_mode,
+            monitors: config.monitors.clone(),
         }
     }
 
@@ -43,19 +154,94 @@ impl SettingsData {
             border_enabled: self.border_enabled,
             flash_enabled: self.flash_enabled,
             indicator_enabled: self.indicator_enabled,
-            border_color: BorderColor::new(
-                self.border_color[0],
-                self.border_color[1],
-                self.border_color[2],
-                0.9,
-            ),
+            theme: self.theme.clone(),
+            accent_enabled: self.accent_enabled,
+            high_contrast: self.high_contrast,
+            border_color: from_rgba(self.border_color),
             border_thickness: self.border_thickness,
             border_style: self.border_style,
+            gradient_stops: self.gradient_stops.iter().copied().map(from_rgba).collect(),
+            gradient_rotate: self.gradient_rotate,
+            gradient_rotation_ms: self.gradient_rotation_ms,
+            dash_length: self.dash_length,
+            dash_gap: self.dash_gap,
+            glow_width: self.glow_width,
+            glow_layers: self.glow_layers,
+            glow_falloff: self.glow_falloff,
+            corner_style: self.corner_style,
+            corner_radius: self.corner_radius,
+            animate_transitions: self.animate_transitions,
+            animation_duration_ms: self.animation_duration_ms,
+            animation_easing: self.animation_easing,
             flash_duration_ms: self.flash_duration_ms,
+            flash_fade_ms: self.flash_fade_ms,
             flash_opacity: self.flash_opacity,
+            flash_color: self.flash_color_custom.then(|| from_rgba(self.flash_color)),
+            flash_shape: self.flash_shape,
+            flash_target: self.flash_target,
+            flash_same_monitor: self.flash_same_monitor,
+            spotlight_enabled: self.spotlight_enabled,
+            spotlight_opacity: self.spotlight_opacity,
+            title_tab_enabled: self.title_tab_enabled,
+            title_tab_show_title: self.title_tab_show_title,
+            title_tab_show_monitor: self.title_tab_show_monitor,
+            indicator_mode: self.indicator_mode,
+            minimap_width: self.minimap_width,
+            minimap_scope: self.minimap_scope,
+            badge_corner: self.badge_corner,
+            badge_offset_x: self.badge_offset_x,
+            badge_offset_y: self.badge_offset_y,
+            badge_width: self.badge_width,
+            badge_height: self.badge_height,
+            badge_shape: self.badge_shape,
+            badge_font_size: self.badge_font_size,
+            badge_color: self.badge_color_custom.then(|| from_rgba(self.badge_color)),
+            badge_inactive_color: from_rgba(self.badge_inactive_color),
+            badge_template: self.badge_template.clone(),
             reveal_hotkey_enabled: self.reveal_hotkey_enabled,
+            picker_hotkey_enabled: self.picker_hotkey_enabled,
+            locator_hotkey_enabled: self.locator_hotkey_enabled,
+            locator_on_shake: self.locator_on_shake,
+            locator_on_monitor_change: self.locator_on_monitor_change,
+            warp_on_monitor_change: self.warp_on_monitor_change,
+            warp_target: self.warp_target,
+            warp_exclude: self
+                .warp_exclude
+                .split(',')
+                .map(str::trim)
+                .filter(|exe| !exe.is_empty())
+                .map(str::to_string)
+                .collect(),
+            warp_hotkey_enabled: self.warp_hotkey_enabled,
             poll_interval_ms: self.poll_interval_ms,
+            render_mode: self.render_mode,
             auto_start: self.auto_start,
+            monitors: self.monitors.clone(),
         }
     }
 }
+
+fn rgba(c: BorderColor) -> [f32; 4] {
+    [c.r, c.g, c.b, c.a]
+}
+
+fn from_rgba([r, g, b, a]: [f32; 4]) -> BorderColor {
+    BorderColor::new(r, g, b, a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_round_trip_with_alpha() {
+        let config = Config {
+            border_color: BorderColor::new(0.1, 0.2, 0.3, 0.45),
+            gradient_stops: vec![BorderColor::new(1.0, 0.0, 0.0, 0.5), BorderColor::new(0.0, 0.0, 1.0, 1.0)],
+            flash_color: Some(BorderColor::new(0.9, 0.8, 0.7, 0.6)),
+            badge_color: Some(BorderColor::new(0.4, 0.5, 0.6, 0.7)),
+            ..Config::default()
+        };
+        assert_eq!(SettingsData::from_config(&config).to_config(), config);
+    }
+}
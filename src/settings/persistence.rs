@@ -1,7 +1,10 @@
 use std::path::PathBuf;
 
+use crate::accent;
+use crate::color;
 use crate::config::Config;
 use crate::settings::autostart;
+use crate::theme::{self, Theme, ThemeColors};
 
 fn config_dir() -> Option<PathBuf> {
     dirs::config_dir().map(|d| d.join("whereismywindow"))
@@ -11,16 +14,108 @@ fn config_path() -> Option<PathBuf> {
     config_dir().map(|d| d.join("config.toml"))
 }
 
+/// Border alpha that files from before themes always stored. Borders were
+/// drawn opaque then, whatever the alpha.
+const LEGACY_BORDER_ALPHA: f32 = 0.9;
+
+/// Parse a config file, taking the themed values it leaves out from its theme.
+fn parse_config(contents: &str) -> Result<Config, toml::de::Error> {
+    let mut table: toml::Table = contents.parse()?;
+    migrate_legacy_alpha(&mut table);
+    let name = table
+        .get("theme")
+        .and_then(|v| v.as_str())
+        .unwrap_or(theme::DEFAULT_THEME)
+        .to_string();
+    match theme::find(&name) {
+        Some(theme) => theme.fill_missing(&mut table),
+        None => log::warn!("Unknown theme '{}'; using defaults for its values", name),
+    }
+    table.try_into()
+}
+
+/// Keep the border of a file saved before themes (it has no `theme` key)
+/// opaque, as it was drawn then, instead of at its stored 90% alpha.
+fn migrate_legacy_alpha(table: &mut toml::Table) {
+    if table.contains_key("theme") {
+        return;
+    }
+    let Some(toml::Value::Table(color)) = table.get_mut("border_color") else { return };
+    if let Some(alpha) = color.get_mut("a") {
+        if alpha.as_float().is_some_and(|a| a as f32 == LEGACY_BORDER_ALPHA) {
+            *alpha = toml::Value::Float(1.0);
+        }
+    }
+}
+
+/// Serialize a config, leaving out the themed values that match its theme
+/// and the colors taken from Windows. Colors are written in the form the
+/// `previous` file used for them.
+fn serialize_config(config: &Config, previous: Option<&str>) -> Result<String, toml::ser::Error> {
+    let mut table = toml::Table::try_from(config)?;
+    if let Some(theme) = theme::find(&config.theme) {
+        theme.strip_matching(&mut table);
+    }
+    if theme::uses_system_colors(config) {
+        for key in accent::PALETTE_KEYS {
+            table.remove(key);
+        }
+    }
+    if let Some(previous) = previous.and_then(|p| p.parse::<toml::Table>().ok()) {
+        color::restore_forms(&mut table, &previous);
+    }
+    toml::to_string_pretty(&table)
+}
+
+/// Themes from `themes/*.toml` in the config directory, named after their
+/// files. Files that fail to parse are skipped.
+pub fn load_user_themes() -> Vec<Theme> {
+    let Some(dir) = config_dir().map(|d| d.join("themes")) else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("toml")))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_string_lossy().into_owned();
+            let contents = std::fs::read_to_string(&path).ok()?;
+            match toml::from_str::<Theme>(&contents) {
+                Ok(theme) => Some(Theme { name, ..theme }),
+                Err(e) => {
+                    log::warn!("Failed to parse theme {}: {}", path.display(), e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
 pub fn load_config() -> Config {
+    load_config_with_theme_colors().0
+}
+
+/// The config, along with its colors from before the accent or high-contrast
+/// palette replaced them.
+pub fn load_config_with_theme_colors() -> (Config, ThemeColors) {
     let Some(path) = config_path() else {
         log::warn!("Could not determine config directory; using defaults");
         let mut cfg = Config::default();
         cfg.auto_start = autostart::is_auto_start();
-        return cfg;
+        let colors = ThemeColors::from_config(&cfg);
+        return (cfg, colors);
     };
 
     let mut cfg = match std::fs::read_to_string(&path) {
-        Ok(contents) => match toml::from_str::<Config>(&contents) {
+        Ok(contents) => match parse_config(&contents) {
             Ok(c) => {
                 log::info!("Config loaded from {}", path.display());
                 c
@@ -38,7 +133,9 @@ pub fn load_config() -> Config {
 
     // Sync auto_start with actual registry state
     cfg.auto_start = autostart::is_auto_start();
-    cfg
+    let colors = ThemeColors::from_config(&cfg);
+    theme::resolve_system_colors(&mut cfg);
+    (cfg, colors)
 }
 
 pub fn save_config(config: &Config) {
@@ -54,7 +151,8 @@ pub fn save_config(config: &Config) {
 
     let Some(path) = config_path() else { return };
 
-    match toml::to_string_pretty(config) {
+    let previous = std::fs::read_to_string(&path).ok();
+    match serialize_config(config, previous.as_deref()) {
         Ok(contents) => {
             if let Err(e) = std::fs::write(&path, contents) {
                 log::warn!("Failed to write config to {}: {}", path.display(), e);
@@ -67,3 +165,39 @@ pub fn save_config(config: &Config) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BorderColor;
+
+    #[test]
+    fn pre_theme_file_keeps_an_opaque_border() {
+        let contents = r#"
+            border_enabled = true
+            border_thickness = 4.0
+
+            [border_color]
+            r = 0.0
+            g = 0.4699999988079071
+            b = 0.8399999737739563
+            a = 0.8999999761581421
+        "#;
+        let config = parse_config(contents).unwrap();
+        assert_eq!(config.border_color, BorderColor::new(0.0, 0.47, 0.84, 1.0));
+    }
+
+    #[test]
+    fn themed_file_keeps_its_border_alpha() {
+        let contents = r#"
+            theme = "Classic"
+
+            [border_color]
+            r = 0.0
+            g = 0.47
+            b = 0.84
+            a = 0.9
+        "#;
+        assert_eq!(parse_config(contents).unwrap().border_color.a, 0.9);
+    }
+}
@@ -2,39 +2,34 @@ use std::sync::mpsc::Sender;
 
 use eframe::egui;
 
-use crate::config::BorderStyle;
+use crate::config::{BadgeCorner, BadgeShape, BorderStyle, IndicatorMode, MiniMapScope, CornerStyle, Easing, FlashShape, FlashTarget, GlowFalloff, HighContrast, RenderMode, WarpTarget};
 use crate::settings::data::{SettingsData, SettingsMessage};
+use crate::contrast;
+use crate::theme::{self, Theme};
 
 pub struct SettingsApp {
     draft: SettingsData,
+    themes: Vec<Theme>,
     tx: Sender<SettingsMessage>,
-    border_style_index: usize,
 }
 
-const BORDER_STYLE_LABELS: [&str; 2] = ["Solid", "Glow"];
-
-fn border_style_to_index(style: BorderStyle) -> usize {
-    match style {
-        BorderStyle::Solid => 0,
-        BorderStyle::Glow => 1,
+impl SettingsApp {
+    pub fn new(data: SettingsData, tx: Sender<SettingsMessage>) -> Self {
+        Self { draft: data, themes: theme::available(), tx }
     }
-}
 
-fn index_to_border_style(index: usize) -> BorderStyle {
-    match index {
-        0 => BorderStyle::Solid,
-        _ => BorderStyle::Glow,
+    /// Switch the draft to `theme`, replacing its themed values; the colors
+    /// still follow the accent or high contrast if those are on.
+    fn apply_theme(&mut self, theme: &Theme) {
+        let mut config = self.draft.to_config();
+        theme.apply(&mut config);
+        theme::resolve_system_colors(&mut config);
+        self.draft = SettingsData::from_config(&config);
     }
-}
 
-impl SettingsApp {
-    pub fn new(data: SettingsData, tx: Sender<SettingsMessage>) -> Self {
-        let border_style_index = border_style_to_index(data.border_style);
-        Self {
-            draft: data,
-            tx,
-            border_style_index,
-        }
+    /// Whether the colors come from Windows and can't be edited.
+    fn colors_locked(&self) -> bool {
+        self.draft.accent_enabled || contrast::is_active(self.draft.high_contrast)
     }
 }
 
@@ -61,21 +56,89 @@ fn render_section<R>(
     result
 }
 
+/// Color button for the RGB part of an RGBA color, leaving its alpha alone.
+fn color_edit_rgb(ui: &mut egui::Ui, rgba: &mut [f32; 4]) {
+    let mut rgb = [rgba[0], rgba[1], rgba[2]];
+    if ui.color_edit_button_rgb(&mut rgb).changed() {
+        rgba[..3].copy_from_slice(&rgb);
+    }
+}
+
 impl eframe::App for SettingsApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let colors_locked = self.colors_locked();
         egui::CentralPanel::default().show(ctx, |ui| {
             egui::ScrollArea::vertical().show(ui, |ui| {
                 ui.heading("WhereIsMyWindow Settings");
                 ui.add_space(12.0);
 
+                // -- Theme --
+                render_section(ui, "Theme", |ui| {
+                    let mut chosen = None;
+                    ui.horizontal(|ui| {
+                        ui.label("Theme:");
+                        egui::ComboBox::from_id_salt("theme")
+                            .selected_text(&self.draft.theme)
+                            .show_ui(ui, |ui| {
+                                for theme in &self.themes {
+                                    let selected = theme.name.eq_ignore_ascii_case(&self.draft.theme);
+                                    if ui.selectable_label(selected, &theme.name).clicked() {
+                                        chosen = Some(theme.clone());
+                                    }
+                                }
+                            });
+                    });
+                    let mut system_changed = ui
+                        .checkbox(&mut self.draft.accent_enabled, "Follow Windows accent color and light/dark mode")
+                        .changed();
+                    ui.horizontal(|ui| {
+                        ui.label("High contrast:");
+                        egui::ComboBox::from_id_salt("high_contrast")
+                            .selected_text(self.draft.high_contrast.label())
+                            .show_ui(ui, |ui| {
+                                for mode in HighContrast::ALL {
+                                    system_changed |= ui
+                                        .selectable_value(&mut self.draft.high_contrast, mode, mode.label())
+                                        .changed();
+                                }
+                            });
+                    });
+                    if system_changed {
+                        // Preview the system colors, or bring the theme's back
+                        chosen = self
+                            .themes
+                            .iter()
+                            .find(|t| t.name.eq_ignore_ascii_case(&self.draft.theme))
+                            .cloned()
+                            .or_else(|| Some(Theme::default()));
+                    }
+                    if let Some(theme) = chosen {
+                        self.apply_theme(&theme);
+                    }
+                    ui.label(
+                        egui::RichText::new(
+                            "Sets the border, badge and flash colors, border style and thickness. \
+                             Changes to those below are kept on top of the theme. \
+                             Colors taken from the accent or high contrast are not saved.",
+                        )
+                        .weak(),
+                    );
+                });
+
                 // -- Border --
                 render_section(ui, "Border", |ui| {
                     ui.checkbox(&mut self.draft.border_enabled, "Enable border");
                     ui.add_space(6.0);
 
-                    ui.horizontal(|ui| {
-                        ui.label("Color:");
-                        ui.color_edit_button_rgb(&mut self.draft.border_color);
+                    ui.add_enabled_ui(!colors_locked, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Color:");
+                            color_edit_rgb(ui, &mut self.draft.border_color);
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Opacity:");
+                            ui.add(egui::Slider::new(&mut self.draft.border_color[3], 0.0..=1.0));
+                        });
                     });
 
                     ui.horizontal(|ui| {
@@ -89,43 +152,404 @@ impl eframe::App for SettingsApp {
                     ui.horizontal(|ui| {
                         ui.label("Style:");
                         egui::ComboBox::from_id_salt("border_style")
-                            .selected_text(BORDER_STYLE_LABELS[self.border_style_index])
+                            .selected_text(self.draft.border_style.label())
+                            .show_ui(ui, |ui| {
+                                for style in BorderStyle::ALL {
+                                    ui.selectable_value(&mut self.draft.border_style, style, style.label());
+                                }
+                            });
+                    });
+
+                    let dashed = matches!(
+                        self.draft.border_style,
+                        BorderStyle::Dashed | BorderStyle::Dotted
+                    );
+                    if dashed {
+                        ui.add_enabled_ui(self.draft.border_style == BorderStyle::Dashed, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label("Dash length:");
+                                ui.add(
+                                    egui::Slider::new(&mut self.draft.dash_length, 2.0..=40.0)
+                                        .suffix(" px"),
+                                );
+                            });
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Gap:");
+                            ui.add(
+                                egui::Slider::new(&mut self.draft.dash_gap, 2.0..=40.0)
+                                    .suffix(" px"),
+                            );
+                        });
+                    }
+
+                    if self.draft.border_style == BorderStyle::Glow {
+                        ui.horizontal(|ui| {
+                            ui.label("Glow width:");
+                            ui.add(
+                                egui::Slider::new(&mut self.draft.glow_width, 2.0..=32.0)
+                                    .suffix(" px"),
+                            );
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Glow layers:");
+                            ui.add(egui::Slider::new(&mut self.draft.glow_layers, 1..=16));
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Falloff:");
+                            egui::ComboBox::from_id_salt("glow_falloff")
+                                .selected_text(self.draft.glow_falloff.label())
+                                .show_ui(ui, |ui| {
+                                    for falloff in GlowFalloff::ALL {
+                                        ui.selectable_value(&mut self.draft.glow_falloff, falloff, falloff.label());
+                                    }
+                                });
+                        });
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.label("Corners:");
+                        egui::ComboBox::from_id_salt("corner_style")
+                            .selected_text(self.draft.corner_style.label())
                             .show_ui(ui, |ui| {
-                                for (i, label) in BORDER_STYLE_LABELS.iter().enumerate() {
-                                    ui.selectable_value(&mut self.border_style_index, i, *label);
+                                for style in CornerStyle::ALL {
+                                    ui.selectable_value(&mut self.draft.corner_style, style, style.label());
                                 }
                             });
-                        self.draft.border_style = index_to_border_style(self.border_style_index);
+                    });
+
+                    ui.add_enabled_ui(self.draft.corner_style == CornerStyle::Manual, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Corner radius:");
+                            ui.add(
+                                egui::Slider::new(&mut self.draft.corner_radius, 0.0..=24.0)
+                                    .suffix(" px"),
+                            );
+                        });
+                    });
+                });
+
+                // -- Gradient --
+                render_section(ui, "Gradient", |ui| {
+                    ui.label("Colors used by the Gradient style, spread around the window.");
+                    ui.add_space(6.0);
+
+                    let can_remove = self.draft.gradient_stops.len() > 2;
+                    let mut remove = None;
+                    for (i, stop) in self.draft.gradient_stops.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("Stop {}:", i + 1));
+                            ui.color_edit_button_rgba_unmultiplied(stop);
+                            if ui.add_enabled(can_remove, egui::Button::new("Remove")).clicked() {
+                                remove = Some(i);
+                            }
+                        });
+                    }
+                    if let Some(i) = remove {
+                        self.draft.gradient_stops.remove(i);
+                    }
+                    if ui.button("Add stop").clicked() {
+                        let last = self.draft.gradient_stops.last().copied().unwrap_or([1.0; 4]);
+                        self.draft.gradient_stops.push(last);
+                    }
+                    ui.add_space(6.0);
+
+                    ui.checkbox(&mut self.draft.gradient_rotate, "Rotate around the window");
+                    ui.add_enabled_ui(self.draft.gradient_rotate, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Revolution:");
+                            let mut secs = self.draft.gradient_rotation_ms as f32 / 1000.0;
+                            ui.add(egui::Slider::new(&mut secs, 1.0..=20.0).suffix(" s"));
+                            self.draft.gradient_rotation_ms = (secs * 1000.0).round() as u32;
+                        });
+                    });
+                });
+
+                // -- Animation --
+                render_section(ui, "Animation", |ui| {
+                    ui.checkbox(
+                        &mut self.draft.animate_transitions,
+                        "Slide border between windows",
+                    );
+                    ui.add_space(6.0);
+
+                    ui.add_enabled_ui(self.draft.animate_transitions, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Duration:");
+                            let mut dur = self.draft.animation_duration_ms as f32;
+                            ui.add(egui::Slider::new(&mut dur, 50.0..=600.0).suffix(" ms"));
+                            self.draft.animation_duration_ms = dur.round() as u32;
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label("Easing:");
+                            egui::ComboBox::from_id_salt("animation_easing")
+                                .selected_text(self.draft.animation_easing.label())
+                                .show_ui(ui, |ui| {
+                                    for easing in Easing::ALL {
+                                        ui.selectable_value(
+                                            &mut self.draft.animation_easing,
+                                            easing,
+                                            easing.label(),
+                                        );
+                                    }
+                                });
+                        });
                     });
                 });
 
                 // -- Flash --
                 render_section(ui, "Flash", |ui| {
                     ui.checkbox(&mut self.draft.flash_enabled, "Flash on monitor change");
+                    ui.add_enabled_ui(self.draft.flash_enabled, |ui| {
+                        ui.checkbox(
+                            &mut self.draft.flash_same_monitor,
+                            "Subtle window flash on same-monitor focus changes",
+                        );
+                    });
                     ui.add_space(6.0);
 
+                    ui.horizontal(|ui| {
+                        ui.label("Target:");
+                        egui::ComboBox::from_id_salt("flash_target")
+                            .selected_text(self.draft.flash_target.label())
+                            .show_ui(ui, |ui| {
+                                for target in FlashTarget::ALL {
+                                    ui.selectable_value(&mut self.draft.flash_target, target, target.label());
+                                }
+                            });
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Shape:");
+                        egui::ComboBox::from_id_salt("flash_shape")
+                            .selected_text(self.draft.flash_shape.label())
+                            .show_ui(ui, |ui| {
+                                for shape in FlashShape::ALL {
+                                    ui.selectable_value(&mut self.draft.flash_shape, shape, shape.label());
+                                }
+                            });
+                    });
+
+                    ui.add_enabled_ui(!colors_locked, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.draft.flash_color_custom, "Custom color");
+                            if self.draft.flash_color_custom {
+                                color_edit_rgb(ui, &mut self.draft.flash_color);
+                            } else {
+                                // Preview the border color the flash will follow
+                                self.draft.flash_color = self.draft.border_color;
+                            }
+                        });
+                    });
+
                     ui.horizontal(|ui| {
                         ui.label("Duration:");
                         let mut dur = self.draft.flash_duration_ms as f32;
-                        ui.add(egui::Slider::new(&mut dur, 50.0..=500.0).suffix(" ms"));
+                        ui.add(egui::Slider::new(&mut dur, 50.0..=1000.0).suffix(" ms"));
                         self.draft.flash_duration_ms = dur.round() as u32;
                     });
 
+                    ui.horizontal(|ui| {
+                        ui.label("Fade-out:");
+                        let mut fade = self.draft.flash_fade_ms.min(self.draft.flash_duration_ms) as f32;
+                        let max = self.draft.flash_duration_ms as f32;
+                        ui.add(egui::Slider::new(&mut fade, 0.0..=max).suffix(" ms"));
+                        self.draft.flash_fade_ms = fade.round() as u32;
+                    });
+
                     ui.horizontal(|ui| {
                         ui.label("Opacity:");
                         ui.add(egui::Slider::new(&mut self.draft.flash_opacity, 0.05..=0.8));
                     });
                 });
 
+                // -- Title Tab --
+                render_section(ui, "Title Tab", |ui| {
+                    ui.checkbox(&mut self.draft.title_tab_enabled, "Show app name on the border");
+                    ui.add_enabled_ui(self.draft.title_tab_enabled, |ui| {
+                        ui.checkbox(&mut self.draft.title_tab_show_title, "Include window title");
+                        ui.checkbox(&mut self.draft.title_tab_show_monitor, "Include monitor number");
+                    });
+                });
+
+                // -- Spotlight --
+                render_section(ui, "Spotlight", |ui| {
+                    ui.checkbox(&mut self.draft.spotlight_enabled, "Dim everything except the focused window");
+                    ui.add_space(6.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label("Opacity:");
+                        ui.add(egui::Slider::new(&mut self.draft.spotlight_opacity, 0.1..=0.9));
+                    });
+                });
+
                 // -- Monitor Indicators --
                 render_section(ui, "Monitor Indicators", |ui| {
-                    ui.checkbox(&mut self.draft.indicator_enabled, "Show monitor badges");
+                    ui.checkbox(&mut self.draft.indicator_enabled, "Show monitor indicator");
+                    ui.add_space(6.0);
+
+                    ui.add_enabled_ui(self.draft.indicator_enabled, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Show:");
+                            egui::ComboBox::from_id_salt("indicator_mode")
+                                .selected_text(self.draft.indicator_mode.label())
+                                .show_ui(ui, |ui| {
+                                    for mode in IndicatorMode::ALL {
+                                        ui.selectable_value(&mut self.draft.indicator_mode, mode, mode.label());
+                                    }
+                                });
+                        });
+
+                        ui.add_enabled_ui(!colors_locked, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.checkbox(&mut self.draft.badge_color_custom, "Custom active color");
+                                if self.draft.badge_color_custom {
+                                    // The active badge is drawn at a fixed opacity
+                                    color_edit_rgb(ui, &mut self.draft.badge_color);
+                                } else {
+                                    // Preview the border color the badge will follow
+                                    self.draft.badge_color = self.draft.border_color;
+                                }
+                            });
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label("Corner:");
+                            egui::ComboBox::from_id_salt("badge_corner")
+                                .selected_text(self.draft.badge_corner.label())
+                                .show_ui(ui, |ui| {
+                                    for corner in BadgeCorner::ALL {
+                                        ui.selectable_value(&mut self.draft.badge_corner, corner, corner.label());
+                                    }
+                                });
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label("Offset:");
+                            ui.add(egui::Slider::new(&mut self.draft.badge_offset_x, 0..=400).prefix("x ").suffix(" px"));
+                            ui.add(egui::Slider::new(&mut self.draft.badge_offset_y, 0..=400).prefix("y ").suffix(" px"));
+                        });
+
+                        if self.draft.indicator_mode == IndicatorMode::MiniMap {
+                            ui.horizontal(|ui| {
+                                ui.label("Map width:");
+                                ui.add(egui::Slider::new(&mut self.draft.minimap_width, 60..=400).suffix(" px"));
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("On:");
+                                egui::ComboBox::from_id_salt("minimap_scope")
+                                    .selected_text(self.draft.minimap_scope.label())
+                                    .show_ui(ui, |ui| {
+                                        for scope in MiniMapScope::ALL {
+                                            ui.selectable_value(&mut self.draft.minimap_scope, scope, scope.label());
+                                        }
+                                    });
+                            });
+                        } else {
+                            ui.add_enabled_ui(!colors_locked, |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label("Inactive color:");
+                                    ui.color_edit_button_rgba_unmultiplied(&mut self.draft.badge_inactive_color);
+                                });
+                            });
+
+                            ui.horizontal(|ui| {
+                                ui.label("Shape:");
+                                egui::ComboBox::from_id_salt("badge_shape")
+                                    .selected_text(self.draft.badge_shape.label())
+                                    .show_ui(ui, |ui| {
+                                        for shape in BadgeShape::ALL {
+                                            ui.selectable_value(&mut self.draft.badge_shape, shape, shape.label());
+                                        }
+                                    });
+                            });
+
+                            ui.horizontal(|ui| {
+                                ui.label("Size:");
+                                ui.add(egui::Slider::new(&mut self.draft.badge_width, 16..=160).prefix("w ").suffix(" px"));
+                                ui.add(egui::Slider::new(&mut self.draft.badge_height, 16..=120).prefix("h ").suffix(" px"));
+                            });
+
+                            ui.horizontal(|ui| {
+                                ui.label("Text:");
+                                ui.text_edit_singleline(&mut self.draft.badge_template);
+                            });
+                            ui.label(
+                                egui::RichText::new("{n} number, {label} label, {w}x{h} resolution, {windows} window count")
+                                    .weak(),
+                            );
+
+                            ui.horizontal(|ui| {
+                                ui.label("Font size:");
+                                ui.add(egui::Slider::new(&mut self.draft.badge_font_size, 8.0..=40.0).suffix(" pt"));
+                            });
+                        }
+                    });
+
+                    if !self.draft.monitors.is_empty() {
+                        ui.add_space(6.0);
+                        ui.label(
+                            egui::RichText::new("Per-monitor overrides from the config file still apply.")
+                                .weak(),
+                        );
+                    }
+                });
+
+                // -- Cursor Locator --
+                render_section(ui, "Cursor Locator", |ui| {
+                    ui.checkbox(&mut self.draft.locator_hotkey_enabled, "Locator hotkey (Ctrl+Alt+M)");
+                    ui.checkbox(&mut self.draft.locator_on_shake, "Ripple when the mouse is shaken");
+                    ui.checkbox(
+                        &mut self.draft.locator_on_monitor_change,
+                        "Ripple when the cursor moves to another monitor",
+                    );
+                });
+
+                // -- Cursor Warp --
+                render_section(ui, "Cursor Warp", |ui| {
+                    ui.checkbox(
+                        &mut self.draft.warp_on_monitor_change,
+                        "Move cursor to the focused window on keyboard monitor switches",
+                    );
+                    ui.checkbox(&mut self.draft.warp_hotkey_enabled, "Warp hotkey (Ctrl+Alt+J)");
+
+                    ui.horizontal(|ui| {
+                        ui.label("Target:");
+                        egui::ComboBox::from_id_salt("warp_target")
+                            .selected_text(self.draft.warp_target.label())
+                            .show_ui(ui, |ui| {
+                                for target in WarpTarget::ALL {
+                                    ui.selectable_value(&mut self.draft.warp_target, target, target.label());
+                                }
+                            });
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Never warp to:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.draft.warp_exclude)
+                                .hint_text("game.exe, obs64.exe"),
+                        );
+                    });
                 });
 
                 // -- General --
                 render_section(ui, "General", |ui| {
                     ui.checkbox(&mut self.draft.auto_start, "Start with Windows");
                     ui.checkbox(&mut self.draft.reveal_hotkey_enabled, "Reveal hotkey (Ctrl+Shift+F)");
+                    ui.checkbox(&mut self.draft.picker_hotkey_enabled, "Window picker hotkey (Ctrl+Alt+Space)");
+
+                    ui.horizontal(|ui| {
+                        ui.label("Rendering:");
+                        egui::ComboBox::from_id_salt("render_mode")
+                            .selected_text(self.draft.render_mode.label())
+                            .show_ui(ui, |ui| {
+                                for mode in RenderMode::ALL {
+                                    ui.selectable_value(&mut self.draft.render_mode, mode, mode.label());
+                                }
+                            });
+                    });
                 });
 
                 ui.add_space(12.0);
@@ -134,7 +558,7 @@ impl eframe::App for SettingsApp {
                 ui.horizontal(|ui| {
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         if ui.button("Apply").clicked() {
-                            let _ = self.tx.send(SettingsMessage::Apply(self.draft.clone()));
+                            let _ = self.tx.send(SettingsMessage::Apply(Box::new(self.draft.clone())));
                             ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                         }
                         if ui.button("Cancel").clicked() {
@@ -0,0 +1,364 @@
+//! Named presets for the border, badge and flash colors.
+//!
+//! A theme bundles the values listed in `THEMED_KEYS`. Built-in themes ship
+//! with the app; more can be added as `themes/<name>.toml` in the config
+//! directory, using the same keys as the config file. A user theme named like
+//! a built-in one replaces it.
+//!
+//! The config file stores the theme name and only those themed values that
+//! differ from the theme, so editing a theme file updates every config that
+//! uses it without overwriting the user's own tweaks.
+
+use serde::{Deserialize, Serialize};
+
+use crate::accent;
+use crate::config::{self, BorderColor, BorderStyle, Config};
+use crate::contrast;
+use crate::settings::persistence;
+
+pub const DEFAULT_THEME: &str = "Classic";
+
+/// Config keys a theme provides.
+const THEMED_KEYS: [&str; 6] = [
+    "border_color",
+    "border_style",
+    "border_thickness",
+    "badge_color",
+    "badge_inactive_color",
+    "flash_color",
+];
+
+/// Values a theme file leaves out come from the Classic theme.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    /// From the file name for user themes.
+    #[serde(skip)]
+    pub name: String,
+    pub border_color: BorderColor,
+    pub border_style: BorderStyle,
+    pub border_thickness: f32,
+    /// Focused-monitor badge color; follows `border_color` when unset.
+    #[serde(deserialize_with = "config::deserialize_follow_border")]
+    pub badge_color: Option<BorderColor>,
+    pub badge_inactive_color: BorderColor,
+    /// Flash color; follows `border_color` when unset.
+    #[serde(deserialize_with = "config::deserialize_follow_border")]
+    pub flash_color: Option<BorderColor>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::from_config(DEFAULT_THEME, &Config::default())
+    }
+}
+
+impl Theme {
+    /// The themed values of `config`, under the name `name`.
+    pub fn from_config(name: &str, config: &Config) -> Self {
+        Self {
+            name: name.to_string(),
+            border_color: config.border_color,
+            border_style: config.border_style,
+            border_thickness: config.border_thickness,
+            badge_color: config.badge_color,
+            badge_inactive_color: config.badge_inactive_color,
+            flash_color: config.flash_color,
+        }
+    }
+
+    /// Switch `config` to this theme, replacing all themed values.
+    pub fn apply(&self, config: &mut Config) {
+        config.theme = self.name.clone();
+        config.border_color = self.border_color;
+        config.border_style = self.border_style;
+        config.border_thickness = self.border_thickness;
+        config.badge_color = self.badge_color;
+        config.badge_inactive_color = self.badge_inactive_color;
+        config.flash_color = self.flash_color;
+    }
+
+    /// Add this theme's values for the themed keys missing from a parsed
+    /// config file.
+    pub fn fill_missing(&self, table: &mut toml::Table) {
+        for (key, value) in self.to_table() {
+            table.entry(key).or_insert(value);
+        }
+    }
+
+    /// Drop the themed keys of a config about to be saved that match this
+    /// theme, keeping only the overrides.
+    ///
+    /// A color the theme sets but the config leaves unset (e.g. `flash_color`
+    /// back to following the border) is written as `config::FOLLOW_BORDER`,
+    /// so the theme's color doesn't fill it back in on load.
+    pub fn strip_matching(&self, table: &mut toml::Table) {
+        let own = self.to_table();
+        for key in THEMED_KEYS {
+            match table.get(key) {
+                Some(value) if own.get(key) == Some(value) => {
+                    table.remove(key);
+                }
+                None if own.contains_key(key) => {
+                    table.insert(key.to_string(), config::FOLLOW_BORDER.into());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn to_table(&self) -> toml::Table {
+        toml::Table::try_from(self).unwrap_or_default()
+    }
+}
+
+/// Themes that ship with the app, Classic (the defaults) first.
+pub fn builtin() -> Vec<Theme> {
+    let classic = Theme::default();
+    let mut themes = vec![classic.clone()];
+    themes.extend([
+        Theme {
+            name: "Ocean".to_string(),
+            border_color: BorderColor::new(0.0, 0.6, 0.75, 1.0),
+            border_style: BorderStyle::Glow,
+            badge_color: Some(BorderColor::new(0.0, 0.45, 0.6, 1.0)),
+            badge_inactive_color: BorderColor::new(0.15, 0.25, 0.3, 0.6),
+            flash_color: Some(BorderColor::new(0.0, 0.75, 0.9, 1.0)),
+            ..classic.clone()
+        },
+        Theme {
+            name: "Sunset".to_string(),
+            border_color: BorderColor::new(0.95, 0.45, 0.15, 1.0),
+            border_thickness: 5.0,
+            badge_color: Some(BorderColor::new(0.85, 0.3, 0.3, 1.0)),
+            badge_inactive_color: BorderColor::new(0.35, 0.25, 0.25, 0.6),
+            flash_color: Some(BorderColor::new(1.0, 0.6, 0.2, 1.0)),
+            ..classic.clone()
+        },
+        Theme {
+            name: "Forest".to_string(),
+            border_color: BorderColor::new(0.2, 0.65, 0.3, 1.0),
+            border_style: BorderStyle::Breathing,
+            badge_inactive_color: BorderColor::new(0.2, 0.3, 0.22, 0.6),
+            ..classic
+        },
+        Theme {
+            name: "Mono".to_string(),
+            border_color: BorderColor::new(0.9, 0.9, 0.9, 1.0),
+            border_style: BorderStyle::Dashed,
+            border_thickness: 3.0,
+            badge_color: Some(BorderColor::new(0.5, 0.5, 0.5, 1.0)),
+            badge_inactive_color: BorderColor::new(0.2, 0.2, 0.2, 0.6),
+            flash_color: Some(BorderColor::new(1.0, 1.0, 1.0, 1.0)),
+        },
+        Theme {
+            name: "Neon".to_string(),
+            border_color: BorderColor::new(0.9, 0.2, 0.7, 1.0),
+            border_style: BorderStyle::Pulse,
+            border_thickness: 6.0,
+            badge_color: Some(BorderColor::new(0.2, 0.9, 0.9, 1.0)),
+            badge_inactive_color: BorderColor::new(0.3, 0.1, 0.3, 0.6),
+            flash_color: Some(BorderColor::new(0.2, 0.9, 0.9, 1.0)),
+        },
+        // Colorblind-safe: the border stands out on light and dark window
+        // chrome, and the badges differ in brightness, not just hue
+        Theme {
+            name: "Deuteranopia".to_string(),
+            border_color: BorderColor::new(0.8, 0.45, 0.0, 1.0),
+            border_style: BorderStyle::Solid,
+            border_thickness: 5.0,
+            badge_color: Some(BorderColor::new(0.0, 0.45, 0.75, 1.0)),
+            badge_inactive_color: BorderColor::new(0.08, 0.08, 0.08, 0.8),
+            flash_color: Some(BorderColor::new(0.8, 0.45, 0.0, 1.0)),
+        },
+        Theme {
+            name: "Protanopia".to_string(),
+            border_color: BorderColor::new(0.0, 0.5, 0.85, 1.0),
+            border_style: BorderStyle::Solid,
+            border_thickness: 5.0,
+            badge_color: Some(BorderColor::new(0.75, 0.45, 0.0, 1.0)),
+            badge_inactive_color: BorderColor::new(0.08, 0.08, 0.08, 0.8),
+            flash_color: Some(BorderColor::new(0.0, 0.5, 0.85, 1.0)),
+        },
+        Theme {
+            name: "Tritanopia".to_string(),
+            border_color: BorderColor::new(0.85, 0.35, 0.1, 1.0),
+            border_style: BorderStyle::Solid,
+            border_thickness: 5.0,
+            badge_color: Some(BorderColor::new(0.8, 0.15, 0.35, 1.0)),
+            badge_inactive_color: BorderColor::new(0.08, 0.08, 0.08, 0.8),
+            flash_color: Some(BorderColor::new(0.85, 0.35, 0.1, 1.0)),
+        },
+    ]);
+    themes
+}
+
+/// Replace the themed colors with the ones Windows dictates: the accent
+/// color when followed, then the high-contrast palette while it applies.
+pub fn resolve_system_colors(config: &mut Config) {
+    accent::resolve(config);
+    contrast::resolve(config);
+}
+
+/// The colors the accent and high-contrast palettes replace, as the theme and
+/// config file set them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThemeColors {
+    pub border_color: BorderColor,
+    pub badge_color: Option<BorderColor>,
+    pub badge_inactive_color: BorderColor,
+    pub flash_color: Option<BorderColor>,
+}
+
+impl ThemeColors {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            border_color: config.border_color,
+            badge_color: config.badge_color,
+            badge_inactive_color: config.badge_inactive_color,
+            flash_color: config.flash_color,
+        }
+    }
+
+    pub fn apply(&self, config: &mut Config) {
+        config.border_color = self.border_color;
+        config.badge_color = self.badge_color;
+        config.badge_inactive_color = self.badge_inactive_color;
+        config.flash_color = self.flash_color;
+    }
+}
+
+/// Like `resolve_system_colors`, but starting from `colors` rather than the
+/// current colors of `config`, which may hold a palette that no longer applies.
+pub fn resolve_system_colors_from(colors: &ThemeColors, config: &mut Config) {
+    colors.apply(config);
+    resolve_system_colors(config);
+}
+
+/// Whether the colors of `config` come from Windows rather than the theme.
+pub fn uses_system_colors(config: &Config) -> bool {
+    config.accent_enabled || contrast::is_active(config.high_contrast)
+}
+
+/// Built-in themes followed by the user's, user files replacing built-ins
+/// of the same name.
+pub fn available() -> Vec<Theme> {
+    merge(builtin(), persistence::load_user_themes())
+}
+
+/// The available theme called `name` (case-insensitive).
+pub fn find(name: &str) -> Option<Theme> {
+    available().into_iter().find(|t| t.name.eq_ignore_ascii_case(name))
+}
+
+fn merge(mut themes: Vec<Theme>, user: Vec<Theme>) -> Vec<Theme> {
+    for theme in user {
+        match themes.iter_mut().find(|t| t.name.eq_ignore_ascii_case(&theme.name)) {
+            Some(existing) => *existing = theme,
+            None => themes.push(theme),
+        }
+    }
+    themes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme(name: &str) -> Theme {
+        builtin().into_iter().find(|t| t.name == name).unwrap()
+    }
+
+    #[test]
+    fn classic_is_the_default_config() {
+        let mut config = Config::default();
+        theme(DEFAULT_THEME).apply(&mut config);
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn builtin_names_are_unique() {
+        let names: Vec<_> = builtin().into_iter().map(|t| t.name.to_lowercase()).collect();
+        for (i, name) in names.iter().enumerate() {
+            assert!(!names[i + 1..].contains(name), "duplicate theme {}", name);
+        }
+    }
+
+    #[test]
+    fn saved_config_keeps_only_overrides() {
+        let ocean = theme("Ocean");
+        let mut config = Config::default();
+        ocean.apply(&mut config);
+        config.border_thickness = 7.0;
+
+        let mut table = toml::Table::try_from(&config).unwrap();
+        ocean.strip_matching(&mut table);
+        assert!(table.contains_key("border_thickness"));
+        assert!(!table.contains_key("border_color"));
+        assert!(!table.contains_key("badge_color"));
+        assert!(table.contains_key("theme"));
+
+        ocean.fill_missing(&mut table);
+        let loaded: Config = table.try_into().unwrap();
+        assert_eq!(loaded, config);
+    }
+
+    #[test]
+    fn saved_config_keeps_following_the_border() {
+        let ocean = theme("Ocean");
+        let mut config = Config::default();
+        ocean.apply(&mut config);
+        config.flash_color = None;
+
+        let mut table = toml::Table::try_from(&config).unwrap();
+        ocean.strip_matching(&mut table);
+        assert_eq!(table["flash_color"].as_str(), Some(config::FOLLOW_BORDER));
+        assert!(!table.contains_key("badge_color"));
+
+        ocean.fill_missing(&mut table);
+        let loaded: Config = table.try_into().unwrap();
+        assert_eq!(loaded.flash_color, None);
+        assert_eq!(loaded, config);
+    }
+
+    #[test]
+    fn theme_edits_reach_configs_using_it() {
+        let mut config = Config::default();
+        theme("Forest").apply(&mut config);
+        let mut table = toml::Table::try_from(&config).unwrap();
+        theme("Forest").strip_matching(&mut table);
+
+        let edited = Theme { border_thickness: 9.0, ..theme("Forest") };
+        edited.fill_missing(&mut table);
+        let loaded: Config = table.try_into().unwrap();
+        assert_eq!(loaded.border_thickness, 9.0);
+        assert_eq!(loaded.border_style, BorderStyle::Breathing);
+    }
+
+    #[test]
+    fn palette_turned_off_restores_theme_colors() {
+        let mut config = Config::default();
+        theme("Ocean").apply(&mut config);
+        let colors = ThemeColors::from_config(&config);
+
+        // Resolved while high contrast was on, then Windows turned it off
+        contrast::palette(false, None).apply(&mut config);
+        config.high_contrast = config::HighContrast::Off;
+        resolve_system_colors_from(&colors, &mut config);
+        assert_eq!(ThemeColors::from_config(&config), colors);
+        assert_eq!(config.border_color, theme("Ocean").border_color);
+    }
+
+    #[test]
+    fn user_theme_replaces_builtin_of_same_name() {
+        let user = vec![
+            Theme { name: "ocean".to_string(), border_thickness: 2.0, ..Theme::default() },
+            Theme { name: "Custom".to_string(), ..Theme::default() },
+        ];
+        let themes = merge(builtin(), user);
+        assert_eq!(themes.len(), builtin().len() + 1);
+        let ocean = themes.iter().find(|t| t.name.eq_ignore_ascii_case("ocean")).unwrap();
+        assert_eq!(ocean.border_thickness, 2.0);
+        assert_eq!(themes.last().unwrap().name, "Custom");
+    }
+}
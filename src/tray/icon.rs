@@ -1,14 +1,18 @@
 use tray_icon::{
-    menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+    menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu},
     TrayIcon, TrayIconBuilder, Icon,
 };
 
 use crate::config::Config;
+use crate::theme;
 
 pub const MENU_TOGGLE_BORDER: &str = "toggle_border";
 pub const MENU_TOGGLE_FLASH: &str = "toggle_flash";
 pub const MENU_TOGGLE_INDICATOR: &str = "toggle_indicator";
+pub const MENU_TOGGLE_SPOTLIGHT: &str = "toggle_spotlight";
 pub const MENU_BORDER_STYLE: &str = "border_style";
+/// Prefix of the theme items' ids; the theme name follows it.
+pub const MENU_THEME_PREFIX: &str = "theme:";
 pub const MENU_SETTINGS: &str = "settings";
 pub const MENU_QUIT: &str = "quit";
 
@@ -18,7 +22,10 @@ pub struct SystemTray {
     pub toggle_border_item: MenuItem,
     pub toggle_flash_item: MenuItem,
     pub toggle_indicator_item: MenuItem,
+    pub toggle_spotlight_item: MenuItem,
     pub border_style_item: MenuItem,
+    /// One check item per available theme, with its name.
+    pub theme_items: Vec<(String, CheckMenuItem)>,
     pub settings_item: MenuItem,
     pub quit_item: MenuItem,
 }
@@ -55,6 +62,27 @@ impl SystemTray {
             true,
             None,
         );
+        let toggle_spotlight_item = MenuItem::with_id(
+            MENU_TOGGLE_SPOTLIGHT,
+            format!("Spotlight: {}", on_off(config.spotlight_enabled)),
+            true,
+            None,
+        );
+        let theme_menu = Submenu::new("Theme", true);
+        let theme_items: Vec<_> = theme::available()
+            .into_iter()
+            .map(|t| {
+                let item = CheckMenuItem::with_id(
+                    format!("{}{}", MENU_THEME_PREFIX, t.name),
+                    &t.name,
+                    true,
+                    t.name.eq_ignore_ascii_case(&config.theme),
+                    None,
+                );
+                let _ = theme_menu.append(&item);
+                (t.name, item)
+            })
+            .collect();
         let settings_item = MenuItem::with_id(
             MENU_SETTINGS,
             "Settings...",
@@ -71,8 +99,10 @@ impl SystemTray {
         let menu = Menu::new();
         let _ = menu.append(&toggle_border_item);
         let _ = menu.append(&border_style_item);
+        let _ = menu.append(&theme_menu);
         let _ = menu.append(&toggle_flash_item);
         let _ = menu.append(&toggle_indicator_item);
+        let _ = menu.append(&toggle_spotlight_item);
         let _ = menu.append(&PredefinedMenuItem::separator());
         let _ = menu.append(&settings_item);
         let _ = menu.append(&PredefinedMenuItem::separator());
@@ -90,7 +120,9 @@ impl SystemTray {
             toggle_border_item,
             toggle_flash_item,
             toggle_indicator_item,
+            toggle_spotlight_item,
             border_style_item,
+            theme_items,
             settings_item,
             quit_item,
         })
@@ -111,9 +143,21 @@ impl SystemTray {
         self.toggle_indicator_item.set_text(text);
     }
 
+    pub fn update_spotlight_text(&self, enabled: bool) {
+        let text = format!("Spotlight: {}", on_off(enabled));
+        self.toggle_spotlight_item.set_text(text);
+    }
+
     pub fn update_border_style_text(&self, label: &str) {
         self.border_style_item.set_text(&format!("Style: {}", label));
     }
+
+    /// Check the item of the theme `name` and uncheck the others.
+    pub fn update_theme(&self, name: &str) {
+        for (theme, item) in &self.theme_items {
+            item.set_checked(theme.eq_ignore_ascii_case(name));
+        }
+    }
 }
 
 /// Create a simple colored icon in memory (16x16 blue square).